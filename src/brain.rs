@@ -0,0 +1,246 @@
+//! Cerebros evolutivos: una pequeña red neuronal por herbívoro cuyos pesos forman
+//! parte de sus genes.
+
+use crate::config::BrainConfig;
+use crate::neat::{InnovationTracker, NeatGenome};
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Entradas de la red, en este orden:
+/// dirección (x, y) y cercanía de la planta más próxima, dirección y cercanía del
+/// depredador más próximo, energía propia y edad relativa.
+pub const INPUTS: usize = 8;
+/// Salidas de la red: dirección deseada (x, y), ganas de comer y de reproducirse.
+pub const OUTPUTS: usize = 4;
+/// Nombre de cada entrada, en el orden de [`Sensors::to_inputs`].
+pub const INPUT_NAMES: [&str; INPUTS] = [
+    "planta x",
+    "planta y",
+    "cercanía planta",
+    "depredador x",
+    "depredador y",
+    "cercanía depredador",
+    "energía",
+    "edad",
+];
+/// Nombre de cada salida.
+pub const OUTPUT_NAMES: [&str; OUTPUTS] = ["rumbo x", "rumbo y", "comer", "reproducirse"];
+
+/// Lo que percibe un herbívoro en un tick, ya normalizado para la red.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sensors {
+    /// Dirección unitaria hacia la planta más cercana, o cero si no ve ninguna.
+    pub plant_direction: Vec2,
+    /// 1 junto a la planta, 0 en el límite del alcance o si no ve ninguna.
+    pub plant_proximity: f32,
+    pub predator_direction: Vec2,
+    pub predator_proximity: f32,
+    /// Energía relativa a la necesaria para reproducirse.
+    pub energy: f32,
+    /// Edad relativa a la edad máxima.
+    pub age: f32,
+}
+
+impl Sensors {
+    pub fn to_inputs(&self) -> [f32; INPUTS] {
+        [
+            self.plant_direction.x,
+            self.plant_direction.y,
+            self.plant_proximity,
+            self.predator_direction.x,
+            self.predator_direction.y,
+            self.predator_proximity,
+            self.energy,
+            self.age,
+        ]
+    }
+}
+
+/// Decisión tomada por la red a partir de los sensores.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BrainOutput {
+    /// Dirección y fuerza del movimiento, con longitud máxima 1.
+    pub steer: Vec2,
+    pub eat: bool,
+    pub reproduce: bool,
+}
+
+/// Traduce las salidas de una red a una decisión.
+pub fn interpret([x, y, eat, reproduce]: [f32; OUTPUTS]) -> BrainOutput {
+    BrainOutput {
+        steer: Vec2::new(x, y).clamp_length_max(1.0),
+        eat: eat > 0.0,
+        reproduce: reproduce > 0.0,
+    }
+}
+
+/// Cerebro heredable: una red de topología fija o un genoma NEAT cuya topología
+/// también evoluciona, según `genome.brain.neat`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Brain {
+    Fixed(NeuralNetwork),
+    Neat(NeatGenome),
+}
+
+impl Brain {
+    /// Cerebro de un fundador.
+    pub fn random(
+        config: &BrainConfig,
+        innovations: &mut InnovationTracker,
+        rng: &mut impl Rng,
+    ) -> Self {
+        match config.neat {
+            Some(_) => Brain::Neat(NeatGenome::minimal(config, innovations, rng)),
+            None => Brain::Fixed(NeuralNetwork::random(config, rng)),
+        }
+    }
+
+    /// Salidas en bruto de la red, en el rango de `tanh`.
+    pub fn outputs(&self, sensors: &Sensors) -> [f32; OUTPUTS] {
+        let inputs = sensors.to_inputs();
+        match self {
+            Brain::Fixed(network) => network.forward(&inputs),
+            Brain::Neat(genome) => genome.activate(&inputs),
+        }
+    }
+
+    pub fn decide(&self, sensors: &Sensors) -> BrainOutput {
+        interpret(self.outputs(sensors))
+    }
+
+    /// Copia mutada; si la configuración cambió de tipo de cerebro, la cría nace con uno nuevo.
+    pub fn mutate(
+        &self,
+        config: &BrainConfig,
+        innovations: &mut InnovationTracker,
+        rng: &mut impl Rng,
+    ) -> Self {
+        match (self, &config.neat) {
+            (Brain::Fixed(network), None) => Brain::Fixed(network.mutate(config, rng)),
+            (Brain::Neat(genome), Some(neat)) => {
+                Brain::Neat(genome.mutate(config, neat, innovations, rng))
+            }
+            _ => Self::random(config, innovations, rng),
+        }
+    }
+
+    /// Cruce de dos cerebros; en NEAT, `fitter` aporta los genes que el otro no tiene.
+    pub fn crossover(fitter: &Self, other: &Self, rng: &mut impl Rng) -> Self {
+        match (fitter, other) {
+            (Brain::Fixed(a), Brain::Fixed(b)) => Brain::Fixed(NeuralNetwork::crossover(a, b, rng)),
+            (Brain::Neat(a), Brain::Neat(b)) => Brain::Neat(NeatGenome::crossover(a, b, rng)),
+            _ => fitter.clone(),
+        }
+    }
+
+    /// Genoma NEAT, si lo es.
+    pub fn neat(&self) -> Option<&NeatGenome> {
+        match self {
+            Brain::Neat(genome) => Some(genome),
+            Brain::Fixed(_) => None,
+        }
+    }
+}
+
+/// Red neuronal de una capa oculta con activación `tanh`.
+///
+/// Los pesos se guardan en un único vector: primero los de la capa oculta (cada
+/// neurona con sus `INPUTS` pesos más el sesgo) y después los de la salida.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeuralNetwork {
+    pub hidden: usize,
+    pub weights: Vec<f32>,
+}
+
+impl NeuralNetwork {
+    /// Número de pesos de una red con `hidden` neuronas ocultas.
+    pub fn weight_count(hidden: usize) -> usize {
+        (INPUTS + 1) * hidden + (hidden + 1) * OUTPUTS
+    }
+
+    /// Red con pesos aleatorios en `±initial_weight`.
+    pub fn random(config: &BrainConfig, rng: &mut impl Rng) -> Self {
+        let w = config.initial_weight;
+        Self {
+            hidden: config.hidden_neurons,
+            weights: (0..Self::weight_count(config.hidden_neurons))
+                .map(|_| rng.random_range(-w..=w))
+                .collect(),
+        }
+    }
+
+    /// Propaga las entradas; una red con un número de pesos incoherente no responde.
+    pub fn forward(&self, inputs: &[f32; INPUTS]) -> [f32; OUTPUTS] {
+        if self.weights.len() != Self::weight_count(self.hidden) {
+            return [0.0; OUTPUTS];
+        }
+        let (hidden_weights, output_weights) = self.weights.split_at((INPUTS + 1) * self.hidden);
+        let hidden: Vec<f32> = hidden_weights
+            .chunks_exact(INPUTS + 1)
+            .map(|w| neuron(w, inputs))
+            .collect();
+        let mut outputs = [0.0; OUTPUTS];
+        for (output, w) in outputs
+            .iter_mut()
+            .zip(output_weights.chunks_exact(self.hidden + 1))
+        {
+            *output = neuron(w, &hidden);
+        }
+        outputs
+    }
+
+    /// Copia con cada peso mutado con probabilidad `weight_mutation_rate`.
+    ///
+    /// Si la configuración cambió de tamaño de capa oculta, la cría nace con una red nueva.
+    pub fn mutate(&self, config: &BrainConfig, rng: &mut impl Rng) -> Self {
+        if self.hidden != config.hidden_neurons {
+            return Self::random(config, rng);
+        }
+        let bound = config.weight_bound;
+        let weights = self
+            .weights
+            .iter()
+            .map(|&w| {
+                if rng.random::<f32>() < config.weight_mutation_rate {
+                    (w + config.weight_mutation.sample(rng)).clamp(-bound, bound)
+                } else {
+                    w
+                }
+            })
+            .collect();
+        Self {
+            hidden: self.hidden,
+            weights,
+        }
+    }
+
+    /// Cruce uniforme peso a peso; si las redes no son compatibles se toma una entera.
+    pub fn crossover(a: &Self, b: &Self, rng: &mut impl Rng) -> Self {
+        if a.hidden != b.hidden || a.weights.len() != b.weights.len() {
+            return if rng.random_bool(0.5) {
+                a.clone()
+            } else {
+                b.clone()
+            };
+        }
+        Self {
+            hidden: a.hidden,
+            weights: a
+                .weights
+                .iter()
+                .zip(&b.weights)
+                .map(|(&wa, &wb)| if rng.random_bool(0.5) { wa } else { wb })
+                .collect(),
+        }
+    }
+}
+
+/// Neurona `tanh`: `weights` contiene un peso por entrada y el sesgo al final.
+fn neuron(weights: &[f32], inputs: &[f32]) -> f32 {
+    let Some((bias, weights)) = weights.split_last() else {
+        return 0.0;
+    };
+    let sum: f32 = weights.iter().zip(inputs).map(|(w, x)| w * x).sum();
+    (sum + bias).tanh()
+}
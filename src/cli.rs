@@ -0,0 +1,147 @@
+use bevy::math::Vec2;
+use std::path::PathBuf;
+
+/// Opciones de línea de comandos del simulador.
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    /// Ejecuta sin ventana ni renderizado.
+    pub headless: bool,
+    /// Tamaño fijo del mundo (ancho x alto) en modo headless.
+    pub world_size: Option<Vec2>,
+    /// Segundos de simulación tras los cuales la aplicación termina.
+    pub duration: Option<f32>,
+    /// Semilla del generador aleatorio; misma semilla, misma historia.
+    pub seed: Option<u64>,
+    /// Ticks de simulación por segundo simulado.
+    pub tick_rate: Option<f64>,
+    /// Archivo RON con los parámetros de la simulación.
+    pub config: Option<PathBuf>,
+    /// Instantánea desde la que continuar la simulación.
+    pub load: Option<PathBuf>,
+    /// Ruta donde guardar una instantánea al salir (y con F5).
+    pub save: Option<PathBuf>,
+    /// Archivo CSV o JSON Lines donde exportar series temporales.
+    pub stats_out: Option<PathBuf>,
+    /// Segundos de simulación entre muestras exportadas.
+    pub stats_interval: Option<f32>,
+    /// Archivo donde exportar el árbol genealógico al salir.
+    pub lineage_out: Option<PathBuf>,
+}
+
+impl CliArgs {
+    pub const USAGE: &'static str = "\
+Uso: simulador_evolutivo [opciones]
+
+Opciones:
+  --headless              Ejecuta sin ventana ni renderizado
+  --world-size <AxB>      Tamaño del mundo, p. ej. 1280x720 (por defecto: 1280x720)
+  --duration <segundos>   Termina tras N segundos de simulación
+  --seed <n>              Semilla para reproducir una ejecución (por defecto: aleatoria)
+  --config <archivo.ron>  Parámetros de la simulación (por defecto: valores integrados)
+  --load <archivo.ron>    Continúa una simulación guardada, con su configuración
+                          (no se combina con --config)
+  --save <archivo.ron>    Guarda una instantánea al salir (F5/F9 usan esta ruta)
+  --stats-out <archivo>   Exporta series temporales (.csv o .jsonl)
+  --stats-interval <s>    Segundos entre muestras exportadas (por defecto: 1)
+  --lineage-out <archivo> Exporta el árbol genealógico al salir (.nwk o .json)
+  --tick-rate <hz>        Ticks de simulación por segundo (por defecto: 60)
+  -h, --help              Muestra esta ayuda";
+
+    /// Lee las opciones de los argumentos del proceso.
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => parsed.headless = true,
+                "--world-size" => {
+                    let value = next_value(&mut args, &arg)?;
+                    parsed.world_size = Some(parse_world_size(&value)?);
+                }
+                "--duration" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let duration = value
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|d| *d > 0.0)
+                        .ok_or_else(|| format!("duración inválida: '{value}'"))?;
+                    parsed.duration = Some(duration);
+                }
+                "--seed" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let seed = value
+                        .parse::<u64>()
+                        .map_err(|_| format!("semilla inválida: '{value}'"))?;
+                    parsed.seed = Some(seed);
+                }
+                "--tick-rate" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let tick_rate = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|hz| *hz > 0.0)
+                        .ok_or_else(|| format!("frecuencia de tick inválida: '{value}'"))?;
+                    parsed.tick_rate = Some(tick_rate);
+                }
+                "--config" => {
+                    parsed.config = Some(PathBuf::from(next_value(&mut args, &arg)?));
+                }
+                "--load" => {
+                    parsed.load = Some(PathBuf::from(next_value(&mut args, &arg)?));
+                }
+                "--save" => {
+                    parsed.save = Some(PathBuf::from(next_value(&mut args, &arg)?));
+                }
+                "--stats-out" => {
+                    parsed.stats_out = Some(PathBuf::from(next_value(&mut args, &arg)?));
+                }
+                "--lineage-out" => {
+                    parsed.lineage_out = Some(PathBuf::from(next_value(&mut args, &arg)?));
+                }
+                "--stats-interval" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let interval = value
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|s| *s > 0.0)
+                        .ok_or_else(|| format!("intervalo de estadísticas inválido: '{value}'"))?;
+                    parsed.stats_interval = Some(interval);
+                }
+                "-h" | "--help" => return Err(Self::USAGE.to_string()),
+                other => return Err(format!("opción desconocida: '{other}'\n\n{}", Self::USAGE)),
+            }
+        }
+
+        // La instantánea trae su propia configuración; con --config la recarga en
+        // caliente volvería a imponer el archivo a mitad de la partida.
+        if parsed.config.is_some() && parsed.load.is_some() {
+            return Err(
+                "--config y --load no se pueden combinar: la instantánea ya incluye su configuración"
+                    .to_string(),
+            );
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("falta el valor de '{flag}'"))
+}
+
+fn parse_world_size(value: &str) -> Result<Vec2, String> {
+    let invalid = || format!("tamaño de mundo inválido: '{value}' (formato: ANCHOxALTO)");
+    let (w, h) = value.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let w = w.trim().parse::<f32>().map_err(|_| invalid())?;
+    let h = h.trim().parse::<f32>().map_err(|_| invalid())?;
+    if w <= 0.0 || h <= 0.0 {
+        return Err(invalid());
+    }
+    Ok(Vec2::new(w, h))
+}
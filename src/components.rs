@@ -1,33 +1,174 @@
+use crate::brain::Brain;
+use crate::config::{CreatureConfig, GenomeConfig, PredatorConfig};
+use crate::lineage::OrganismId;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Organism {
+    /// Identificador estable en el [`LineageStore`](crate::lineage::LineageStore).
+    pub id: OrganismId,
     pub energy: f32,
     pub age: f32,
     pub generation: u32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Creature {
     pub time_since_reproduction: f32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Predator {
     pub reproduction_cooldown: f32,
+    /// Segundos que lleva sujetando a su presa actual.
+    #[serde(default)]
+    pub grip: f32,
 }
 
 #[derive(Component)]
 pub struct Velocity(pub Vec2);
 
-#[derive(Clone, Component)]
+/// Lo que ve un organismo en el tick actual; lo rellena `perception_system` y lo
+/// leen los sistemas de comportamiento en lugar de consultar el mundo entero.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Perception {
+    /// Alcance de la vista.
+    pub range: f32,
+    /// Ángulo de visión en radianes (`TAU` = ve a su alrededor).
+    pub field_of_view: f32,
+    /// Hacia dónde mira: su última dirección de movimiento, o cero si nunca se movió.
+    pub heading: Vec2,
+    /// Planta visible más cercana (herbívoros).
+    pub plant: Option<Percept>,
+    /// Depredador visible más cercano (herbívoros).
+    pub predator: Option<Percept>,
+    /// Herbívoro visible más cercano (depredadores).
+    pub prey: Option<Percept>,
+}
+
+impl Perception {
+    /// Indica si desde `from` se ve un punto en `target`: dentro del alcance y del
+    /// cono de visión centrado en `heading`.
+    pub fn sees(&self, from: Vec2, target: Vec2) -> bool {
+        let offset = target - from;
+        if offset.length_squared() >= self.range * self.range {
+            return false;
+        }
+        if self.field_of_view >= std::f32::consts::TAU || self.heading == Vec2::ZERO {
+            return true;
+        }
+        offset == Vec2::ZERO || self.heading.angle_to(offset).abs() <= self.field_of_view / 2.0
+    }
+}
+
+/// Un objeto percibido: su entidad y dónde cree el organismo que está.
+#[derive(Clone, Copy, Debug)]
+pub struct Percept {
+    pub entity: Entity,
+    /// Posición percibida, con el ruido de percepción aplicado.
+    pub position: Vec2,
+    /// Distancia a la posición percibida.
+    pub distance: f32,
+}
+
+/// Genoma de un organismo: rasgos numéricos por nombre más su color.
+///
+/// Los rasgos disponibles los definen `genome.traits` (herbívoros) y
+/// `predators.genome.traits` (depredadores) en la configuración.
+#[derive(Clone, Debug, Component)]
 pub struct Genes {
-    pub speed: f32,
-    pub size: f32,
+    pub traits: BTreeMap<String, f32>,
     pub color: Color,
+    /// Red neuronal que controla su comportamiento, si `genome.brain` está activo.
+    pub brain: Option<Brain>,
+}
+
+impl Genes {
+    /// Velocidad de crucero. Obligatorio.
+    pub const SPEED: &'static str = "speed";
+    /// Tamaño corporal. Obligatorio.
+    pub const SIZE: &'static str = "size";
+    /// Alcance de la vista de un herbívoro; sustituye a `creatures.vision_range`.
+    pub const VISION_RANGE: &'static str = "vision_range";
+    /// Ángulo de visión en grados; sustituye a `creatures.field_of_view` o `predators.field_of_view`.
+    pub const FIELD_OF_VIEW: &'static str = "field_of_view";
+    /// Edad máxima; sustituye a `creatures.max_age`.
+    pub const LIFESPAN: &'static str = "lifespan";
+    /// Energía por debajo de la cual busca comida; sustituye a `creatures.hunger_threshold`.
+    pub const HUNGER_THRESHOLD: &'static str = "hunger_threshold";
+    /// Energía necesaria para reproducirse; sustituye a `creatures.reproduction_energy`.
+    pub const REPRODUCTION_ENERGY: &'static str = "reproduction_energy";
+    /// Radio en el que un depredador detecta presas; sustituye a `predators.hunt_radius`.
+    pub const HUNT_RADIUS: &'static str = "hunt_radius";
+    /// Distancia a la que un depredador sujeta a su presa; sustituye a `predators.attack_radius`.
+    pub const ATTACK_RADIUS: &'static str = "attack_radius";
+    /// Gasto basal de un depredador; sustituye a `predators.energy_drain`.
+    pub const METABOLISM: &'static str = "metabolism";
+
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.traits.get(name).copied()
+    }
+
+    /// Valor del rasgo o `default` si este genoma no lo tiene.
+    pub fn get_or(&self, name: &str, default: f32) -> f32 {
+        self.get(name).unwrap_or(default)
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.get_or(Self::SPEED, 0.0)
+    }
+
+    pub fn size(&self) -> f32 {
+        self.get_or(Self::SIZE, 0.0)
+    }
+
+    /// Distancia genética entre 0 y 1: diferencia media de los rasgos configurados,
+    /// cada una relativa a la amplitud de sus límites. Los rasgos que falten en
+    /// alguno de los dos genomas no cuentan.
+    pub fn distance(&self, other: &Genes, genome: &GenomeConfig) -> f32 {
+        let (mut total, mut count) = (0.0, 0);
+        for t in &genome.traits {
+            if let (Some(a), Some(b)) = (self.get(&t.name), other.get(&t.name)) {
+                let span = t.bounds.max - t.bounds.min;
+                if span > 0.0 {
+                    total += ((a - b).abs() / span).min(1.0);
+                }
+                count += 1;
+            }
+        }
+        if count == 0 {
+            0.0
+        } else {
+            total / count as f32
+        }
+    }
+
+    /// Energía por segundo que gasta este cuerpo moviéndose a `speed`.
+    pub fn metabolic_drain(&self, config: &CreatureConfig, speed: f32) -> f32 {
+        config.energy_drain + config.size_drain * self.size() + config.speed_drain * speed
+    }
+
+    /// Energía por segundo que gasta un depredador con este cuerpo moviéndose a `speed`.
+    pub fn predator_drain(&self, config: &PredatorConfig, speed: f32) -> f32 {
+        self.get_or(Self::METABOLISM, config.energy_drain)
+            + config.size_drain * self.size()
+            + config.speed_drain * speed
+    }
+
+    /// Distancia a la que alcanza una planta: su radio corporal más el alcance configurado.
+    pub fn eat_radius(&self, config: &CreatureConfig) -> f32 {
+        self.size() * 0.5 + config.eat_radius
+    }
+
+    /// Segundos que un depredador debe sujetarlo para devorarlo.
+    pub fn grip_time(&self, config: &PredatorConfig) -> f32 {
+        self.size() * config.grip_time_per_size
+    }
 }
 
-#[derive(Component, PartialEq, Eq, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum State {
     Wandering,
     SeekingFood,
@@ -39,6 +180,9 @@ pub enum State {
 pub struct FpsText;
 
 #[derive(Component)]
+pub struct HudText;
+
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Plant {
     pub age: f32,
     pub max_age: f32,
@@ -0,0 +1,1041 @@
+use crate::components::Genes;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Intervalo cerrado `[min, max]` de valores permitidos.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValueRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ValueRange {
+    pub const fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+
+    /// Valor aleatorio dentro del intervalo (acepta `min == max`).
+    pub fn sample(&self, rng: &mut impl rand::Rng) -> f32 {
+        rng.random_range(self.min..=self.max)
+    }
+}
+
+/// Parámetros de la simulación, cargados desde un archivo RON o con valores por defecto.
+///
+/// Cualquier campo omitido en el archivo toma su valor por defecto.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimConfig {
+    pub population: PopulationConfig,
+    pub creatures: CreatureConfig,
+    pub predators: PredatorConfig,
+    pub plants: PlantConfig,
+    pub genome: GenomeConfig,
+    pub mating: MatingConfig,
+    pub collisions: CollisionConfig,
+    pub spatial: SpatialConfig,
+    /// Con `Some`, el mundo tiene campos de olor que los herbívoros siguen cuando no
+    /// ven su objetivo (véase [`crate::resources::PheromoneField`]).
+    pub pheromones: Option<PheromoneConfig>,
+}
+
+/// Población inicial y zona donde aparece.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PopulationConfig {
+    pub initial_creatures: usize,
+    pub initial_predators: usize,
+    pub initial_plants: usize,
+    /// Media anchura de la zona de aparición, centrada en el origen.
+    pub spawn_half_width: f32,
+    /// Media altura de la zona de aparición, centrada en el origen.
+    pub spawn_half_height: f32,
+}
+
+impl Default for PopulationConfig {
+    fn default() -> Self {
+        Self {
+            initial_creatures: 10,
+            initial_predators: 2,
+            initial_plants: 30,
+            spawn_half_width: 300.0,
+            spawn_half_height: 200.0,
+        }
+    }
+}
+
+/// Herbívoros: metabolismo, comportamiento y reproducción.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CreatureConfig {
+    pub initial_energy: f32,
+    /// Energía consumida por segundo en reposo, sea cual sea el cuerpo.
+    pub energy_drain: f32,
+    /// Energía extra por segundo por cada unidad de `Genes::size`.
+    pub size_drain: f32,
+    /// Energía extra por segundo por cada unidad de velocidad a la que se mueven.
+    pub speed_drain: f32,
+    /// Edad (segundos) a la que mueren de viejas.
+    pub max_age: f32,
+    /// Por debajo de esta energía buscan comida.
+    pub hunger_threshold: f32,
+    /// Por encima de esta energía pueden reproducirse.
+    pub reproduction_energy: f32,
+    /// Segundos mínimos entre reproducciones.
+    pub reproduction_cooldown: f32,
+    /// Energía que paga el progenitor por cada cría.
+    pub reproduction_cost: f32,
+    /// Ruido máximo añadido a cada componente de la velocidad de la cría.
+    pub child_velocity_noise: f32,
+    /// Velocidad al buscar comida, como múltiplo de `Genes::speed`.
+    pub seek_speed_factor: f32,
+    /// Velocidad al huir, como múltiplo de `Genes::speed`.
+    pub flee_speed_factor: f32,
+    /// Distancia a la que huyen de un depredador que ven.
+    pub flee_radius: f32,
+    /// Alcance de la vista si el genoma no tiene `vision_range`.
+    pub vision_range: f32,
+    /// Ángulo de visión, en grados, si el genoma no tiene `field_of_view`.
+    pub field_of_view: f32,
+    /// Error típico de la posición percibida, como fracción de la distancia real.
+    pub perception_noise: f32,
+    /// Alcance para comerse una planta más allá del borde del cuerpo (la mitad de `Genes::size`).
+    pub eat_radius: f32,
+}
+
+impl Default for CreatureConfig {
+    fn default() -> Self {
+        Self {
+            initial_energy: 100.0,
+            energy_drain: 0.4,
+            size_drain: 0.02,
+            speed_drain: 0.005,
+            max_age: 60.0,
+            hunger_threshold: 50.0,
+            reproduction_energy: 120.0,
+            reproduction_cooldown: 5.0,
+            reproduction_cost: 40.0,
+            child_velocity_noise: 5.0,
+            seek_speed_factor: 1.2,
+            flee_speed_factor: 1.6,
+            flee_radius: 100.0,
+            vision_range: 200.0,
+            field_of_view: 360.0,
+            perception_noise: 0.0,
+            eat_radius: 5.0,
+        }
+    }
+}
+
+/// Depredadores: metabolismo, caza y reproducción.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PredatorConfig {
+    pub initial_energy: f32,
+    /// Energía consumida por segundo en reposo si el genoma no tiene `metabolism`.
+    pub energy_drain: f32,
+    /// Gasto adicional por segundo por cada unidad de tamaño.
+    pub size_drain: f32,
+    /// Gasto adicional por segundo por cada unidad de velocidad a la que se mueven.
+    pub speed_drain: f32,
+    /// Energía máxima acumulable.
+    pub max_energy: f32,
+    /// Solo cazan por debajo de esta energía.
+    pub hunger_threshold: f32,
+    /// Por encima de esta energía entran en temporada de reproducción.
+    pub season_energy: f32,
+    /// Alcance de la vista, y por tanto de la caza, si el genoma no tiene `hunt_radius`.
+    pub hunt_radius: f32,
+    /// Ángulo de visión, en grados, si el genoma no tiene `field_of_view`.
+    pub field_of_view: f32,
+    /// Error típico de la posición percibida, como fracción de la distancia real.
+    pub perception_noise: f32,
+    /// Al cazar corren a este múltiplo de su velocidad de crucero.
+    pub hunt_speed_factor: f32,
+    /// Distancia a la que atrapan a la presa si el genoma no tiene `attack_radius`.
+    pub attack_radius: f32,
+    /// Segundos que deben sujetar a la presa por cada unidad de su `Genes::size`
+    /// antes de devorarla; si escapa del radio de ataque, vuelven a empezar.
+    pub grip_time_per_size: f32,
+    /// Energía obtenida por presa.
+    pub prey_energy: f32,
+    /// Segundos entre reproducciones (también el de las crías al nacer).
+    pub reproduction_cooldown: f32,
+    /// Energía que paga cada progenitor al aparearse.
+    pub reproduction_cost: f32,
+    /// Distancia máxima a la que un depredador en temporada busca pareja; además debe
+    /// verla, así que tampoco pasa de su alcance de vista (`hunt_radius`).
+    pub mate_search_radius: f32,
+    /// Distancia a la que dos depredadores en temporada se aparean.
+    pub mate_radius: f32,
+    /// Rasgos heredables de los depredadores.
+    pub genome: GenomeConfig,
+}
+
+impl Default for PredatorConfig {
+    fn default() -> Self {
+        Self {
+            initial_energy: 100.0,
+            energy_drain: 0.8,
+            size_drain: 0.005,
+            speed_drain: 0.0025,
+            max_energy: 150.0,
+            hunger_threshold: 80.0,
+            season_energy: 110.0,
+            hunt_radius: 150.0,
+            field_of_view: 360.0,
+            perception_noise: 0.0,
+            hunt_speed_factor: 1.125,
+            attack_radius: 25.0,
+            grip_time_per_size: 0.02,
+            prey_energy: 40.0,
+            reproduction_cooldown: 10.0,
+            reproduction_cost: 20.0,
+            mate_search_radius: 400.0,
+            mate_radius: 40.0,
+            genome: GenomeConfig::predators(),
+        }
+    }
+}
+
+/// Plantas: crecimiento, vida y dispersión.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlantConfig {
+    pub initial_size: f32,
+    pub max_size: f32,
+    /// Crecimiento en tamaño por segundo.
+    pub growth_rate: f32,
+    /// Intervalo del que se sortea la edad máxima de cada planta.
+    pub lifespan: ValueRange,
+    /// Segundos entre semillas.
+    pub reproduction_interval: f32,
+    /// Distancia máxima (por eje) a la que cae una semilla.
+    pub seed_spread: f32,
+    /// Energía que aporta a quien se la come.
+    pub energy_value: f32,
+}
+
+impl Default for PlantConfig {
+    fn default() -> Self {
+        Self {
+            initial_size: 10.0,
+            max_size: 15.0,
+            growth_rate: 2.0,
+            lifespan: ValueRange::new(30.0, 60.0),
+            reproduction_interval: 12.0,
+            seed_spread: 50.0,
+            energy_value: 40.0,
+        }
+    }
+}
+
+/// Rasgos heredables de los herbívoros.
+///
+/// Cada rasgo se guarda por nombre en `Genes`; para añadir uno basta con declararlo
+/// aquí. Los sistemas leen los rasgos que conocen (véanse las constantes de `Genes`)
+/// y usan el valor de configuración equivalente cuando un rasgo opcional no existe.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenomeConfig {
+    pub traits: Vec<TraitConfig>,
+    pub color: ColorGeneConfig,
+    /// Si existe, cada organismo lleva en los genes una red neuronal que decide su
+    /// comportamiento en lugar de la máquina de estados. Solo herbívoros.
+    pub brain: Option<BrainConfig>,
+}
+
+impl Default for GenomeConfig {
+    fn default() -> Self {
+        Self {
+            traits: vec![
+                TraitConfig::uniform(Genes::SPEED, (20.0, 60.0), (10.0, 100.0), 5.0),
+                TraitConfig::uniform(Genes::SIZE, (10.0, 30.0), (5.0, 50.0), 2.0),
+            ],
+            color: ColorGeneConfig::default(),
+            brain: None,
+        }
+    }
+}
+
+impl GenomeConfig {
+    /// Genoma por defecto de los depredadores.
+    pub fn predators() -> Self {
+        Self {
+            traits: vec![
+                TraitConfig::uniform(Genes::SPEED, (70.0, 90.0), (30.0, 160.0), 5.0),
+                TraitConfig::uniform(Genes::SIZE, (35.0, 45.0), (15.0, 70.0), 2.0),
+                TraitConfig::uniform(Genes::HUNT_RADIUS, (130.0, 170.0), (50.0, 300.0), 10.0),
+                TraitConfig::uniform(Genes::ATTACK_RADIUS, (20.0, 30.0), (10.0, 50.0), 2.0),
+                TraitConfig::uniform(Genes::METABOLISM, (0.4, 0.5), (0.1, 2.0), 0.05),
+            ],
+            color: ColorGeneConfig::default(),
+            brain: None,
+        }
+    }
+
+    /// Añade a `problems` los errores de este genoma; `section` es su ruta en el archivo.
+    fn check(&self, section: &str, problems: &mut Vec<String>) {
+        let mut names = std::collections::BTreeSet::new();
+        for t in &self.traits {
+            if t.name.is_empty() {
+                problems.push(format!("{section}.traits contiene un rasgo sin nombre"));
+            } else if !names.insert(t.name.as_str()) {
+                problems.push(format!("{section}.traits repite el rasgo '{}'", t.name));
+            }
+            if !(0.0..=1.0).contains(&t.mutation_rate) {
+                problems.push(format!(
+                    "{section}.{}.mutation_rate debe estar entre 0 y 1 (valor: {})",
+                    t.name, t.mutation_rate
+                ));
+            }
+            let spread = t.mutation.spread();
+            if !(spread >= 0.0 && spread.is_finite()) {
+                problems.push(format!(
+                    "{section}.{}.mutation no puede tener una variación negativa (valor: {spread})",
+                    t.name
+                ));
+            }
+            check_range(
+                &format!("{section}.{}.initial", t.name),
+                t.initial,
+                problems,
+            );
+            check_range(&format!("{section}.{}.bounds", t.name), t.bounds, problems);
+            if t.initial.min < t.bounds.min || t.initial.max > t.bounds.max {
+                problems.push(format!(
+                    "{section}.{}.initial debe quedar dentro de {section}.{}.bounds",
+                    t.name, t.name
+                ));
+            }
+        }
+        for required in [Genes::SPEED, Genes::SIZE] {
+            if !names.contains(required) {
+                problems.push(format!(
+                    "{section}.traits debe incluir el rasgo '{required}'"
+                ));
+            }
+        }
+        let color = &self.color;
+        for (name, range) in [
+            ("saturation_bounds", color.saturation_bounds),
+            ("lightness_bounds", color.lightness_bounds),
+        ] {
+            check_range(&format!("{section}.color.{name}"), range, problems);
+            if range.min < 0.0 || range.max > 1.0 {
+                problems.push(format!(
+                    "{section}.color.{name} debe estar entre 0 y 1 (valor: {}–{})",
+                    range.min, range.max
+                ));
+            }
+        }
+        for (name, step) in [
+            ("hue_step", color.hue_step),
+            ("saturation_step", color.saturation_step),
+            ("lightness_step", color.lightness_step),
+        ] {
+            if !(step >= 0.0 && step.is_finite()) {
+                problems.push(format!(
+                    "{section}.color.{name} no puede ser negativo (valor: {step})"
+                ));
+            }
+        }
+        if let Some(brain) = &self.brain {
+            brain.check(&format!("{section}.brain"), problems);
+        }
+        if let Some(marker) = &color.marker
+            && !names.contains(marker.as_str())
+        {
+            problems.push(format!(
+                "{section}.color.marker se refiere al rasgo '{marker}', que no está en {section}.traits"
+            ));
+        }
+    }
+}
+
+/// Red neuronal heredable de los herbívoros (véase [`crate::brain`]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BrainConfig {
+    pub hidden_neurons: usize,
+    /// Los fundadores sortean sus pesos en `±initial_weight`.
+    pub initial_weight: f32,
+    /// Probabilidad (0–1) de que mute cada peso al nacer.
+    pub weight_mutation_rate: f32,
+    pub weight_mutation: MutationDistribution,
+    /// Ningún peso supera `±weight_bound` tras mutar.
+    pub weight_bound: f32,
+    /// Con `Some`, la red tiene topología evolutiva al estilo NEAT (véase
+    /// [`crate::neat`]) y `hidden_neurons` se ignora.
+    pub neat: Option<NeatConfig>,
+}
+
+impl Default for BrainConfig {
+    fn default() -> Self {
+        Self {
+            hidden_neurons: 6,
+            initial_weight: 1.0,
+            weight_mutation_rate: 0.1,
+            weight_mutation: MutationDistribution::Gaussian { sigma: 0.3 },
+            weight_bound: 4.0,
+            neat: None,
+        }
+    }
+}
+
+impl BrainConfig {
+    fn check(&self, section: &str, problems: &mut Vec<String>) {
+        // Los cerebros NEAT parten sin neuronas ocultas e ignoran `hidden_neurons`.
+        if self.neat.is_none() && self.hidden_neurons == 0 {
+            problems.push(format!("{section}.hidden_neurons debe ser mayor que 0"));
+        }
+        if !(self.weight_bound > 0.0 && self.weight_bound.is_finite()) {
+            problems.push(format!(
+                "{section}.weight_bound debe ser mayor que 0 (valor: {})",
+                self.weight_bound
+            ));
+        }
+        if !(self.initial_weight >= 0.0 && self.initial_weight <= self.weight_bound) {
+            problems.push(format!(
+                "{section}.initial_weight debe estar entre 0 y weight_bound (valor: {})",
+                self.initial_weight
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.weight_mutation_rate) {
+            problems.push(format!(
+                "{section}.weight_mutation_rate debe estar entre 0 y 1 (valor: {})",
+                self.weight_mutation_rate
+            ));
+        }
+        let spread = self.weight_mutation.spread();
+        if !(spread >= 0.0 && spread.is_finite()) {
+            problems.push(format!(
+                "{section}.weight_mutation no puede tener una variación negativa (valor: {spread})"
+            ));
+        }
+        if let Some(neat) = &self.neat {
+            neat.check(&format!("{section}.neat"), problems);
+        }
+    }
+}
+
+/// Mutaciones estructurales y especiación de los cerebros NEAT.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NeatConfig {
+    /// Probabilidad (0–1) de que una cría gane una conexión nueva.
+    pub add_connection_rate: f32,
+    /// Probabilidad (0–1) de que una cría parta una conexión con una neurona nueva.
+    pub add_node_rate: f32,
+    /// Distancia genómica por debajo de la cual dos cerebros son de la misma especie.
+    pub compatibility_threshold: f32,
+    /// Peso de los genes sobrantes en la distancia genómica.
+    pub excess_coefficient: f32,
+    /// Peso de los genes disjuntos.
+    pub disjoint_coefficient: f32,
+    /// Peso de la diferencia media de pesos de los genes comunes.
+    pub weight_coefficient: f32,
+}
+
+impl Default for NeatConfig {
+    fn default() -> Self {
+        Self {
+            add_connection_rate: 0.05,
+            add_node_rate: 0.03,
+            compatibility_threshold: 3.0,
+            excess_coefficient: 1.0,
+            disjoint_coefficient: 1.0,
+            weight_coefficient: 0.4,
+        }
+    }
+}
+
+impl NeatConfig {
+    fn check(&self, section: &str, problems: &mut Vec<String>) {
+        for (name, value) in [
+            ("add_connection_rate", self.add_connection_rate),
+            ("add_node_rate", self.add_node_rate),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                problems.push(format!(
+                    "{section}.{name} debe estar entre 0 y 1 (valor: {value})"
+                ));
+            }
+        }
+        if !(self.compatibility_threshold > 0.0 && self.compatibility_threshold.is_finite()) {
+            problems.push(format!(
+                "{section}.compatibility_threshold debe ser mayor que 0 (valor: {})",
+                self.compatibility_threshold
+            ));
+        }
+        for (name, value) in [
+            ("excess_coefficient", self.excess_coefficient),
+            ("disjoint_coefficient", self.disjoint_coefficient),
+            ("weight_coefficient", self.weight_coefficient),
+        ] {
+            if !(value >= 0.0 && value.is_finite()) {
+                problems.push(format!(
+                    "{section}.{name} no puede ser negativo (valor: {value})"
+                ));
+            }
+        }
+    }
+}
+
+/// Color heredable: los fundadores reciben un tono al azar y cada cría se aparta
+/// del de su progenitor en pequeños pasos HSL, así que los linajes se distinguen
+/// a simple vista.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorGeneConfig {
+    /// Variación máxima del tono por generación, en grados.
+    pub hue_step: f32,
+    pub saturation_step: f32,
+    pub lightness_step: f32,
+    /// Límites de saturación y luminosidad (0–1); el tono da la vuelta en 360°.
+    pub saturation_bounds: ValueRange,
+    pub lightness_bounds: ValueRange,
+    /// Rasgo de `traits` que fija el tono (su valor módulo 360) en lugar de la
+    /// deriva propia del color. Pensado para un marcador neutro, sin efecto en la
+    /// supervivencia, cuyo valor se lee directamente en pantalla.
+    pub marker: Option<String>,
+}
+
+impl Default for ColorGeneConfig {
+    fn default() -> Self {
+        Self {
+            hue_step: 8.0,
+            saturation_step: 0.03,
+            lightness_step: 0.03,
+            saturation_bounds: ValueRange::new(0.6, 0.95),
+            lightness_bounds: ValueRange::new(0.45, 0.7),
+            marker: None,
+        }
+    }
+}
+
+/// Un rasgo heredable: valores iniciales, límites y cómo muta.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TraitConfig {
+    pub name: String,
+    /// Intervalo del que se sortea el valor de los fundadores.
+    pub initial: ValueRange,
+    /// Límites que nunca supera tras mutar.
+    pub bounds: ValueRange,
+    /// Probabilidad (0–1) de que el rasgo mute en cada nacimiento.
+    pub mutation_rate: f32,
+    pub mutation: MutationDistribution,
+}
+
+impl TraitConfig {
+    /// Rasgo que muta siempre con variación uniforme de `±step`.
+    pub fn uniform(name: &str, initial: (f32, f32), bounds: (f32, f32), step: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            initial: ValueRange::new(initial.0, initial.1),
+            bounds: ValueRange::new(bounds.0, bounds.1),
+            mutation_rate: 1.0,
+            mutation: MutationDistribution::Uniform { step },
+        }
+    }
+}
+
+/// Distribución de la variación que sufre un rasgo al mutar.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MutationDistribution {
+    /// Variación uniforme en `[-step, step]`.
+    Uniform { step: f32 },
+    /// Variación normal de media 0 y desviación `sigma`.
+    Gaussian { sigma: f32 },
+}
+
+impl MutationDistribution {
+    /// Variación aleatoria a sumar al valor heredado.
+    pub fn sample(&self, rng: &mut impl rand::Rng) -> f32 {
+        match *self {
+            MutationDistribution::Uniform { step } => rng.random_range(-step..=step),
+            MutationDistribution::Gaussian { sigma } => {
+                // Box-Muller; `1 - u` evita el logaritmo de 0.
+                let u: f32 = 1.0 - rng.random::<f32>();
+                let v: f32 = rng.random();
+                sigma * (-2.0 * u.ln()).sqrt() * (std::f32::consts::TAU * v).cos()
+            }
+        }
+    }
+
+    fn spread(&self) -> f32 {
+        match *self {
+            MutationDistribution::Uniform { step } => step,
+            MutationDistribution::Gaussian { sigma } => sigma,
+        }
+    }
+}
+
+/// Reproducción sexual de los herbívoros.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatingConfig {
+    /// Si es `false`, cada herbívoro se reproduce solo clonándose con mutaciones.
+    pub sexual: bool,
+    /// Distancia máxima a la que un herbívoro en celo busca pareja; además tiene que verla.
+    pub search_radius: f32,
+    /// Distancia a la que dos parejas llegan a aparearse.
+    pub mate_radius: f32,
+    /// Distancia genética máxima (0–1) entre parejas compatibles; véase `Genes::distance`.
+    pub max_genetic_distance: f32,
+}
+
+impl Default for MatingConfig {
+    fn default() -> Self {
+        Self {
+            sexual: false,
+            search_radius: 150.0,
+            mate_radius: 20.0,
+            max_genetic_distance: 0.25,
+        }
+    }
+}
+
+/// Separación entre organismos.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CollisionConfig {
+    pub min_separation_distance: f32,
+    pub separation_force: f32,
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            min_separation_distance: 15.0,
+            separation_force: 20.0,
+        }
+    }
+}
+
+/// Índice espacial usado para consultas de vecinos.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpatialConfig {
+    /// Lado de cada celda; conviene que se parezca a los radios de interacción habituales.
+    pub cell_size: f32,
+}
+
+impl Default for SpatialConfig {
+    fn default() -> Self {
+        Self { cell_size: 50.0 }
+    }
+}
+
+/// Campos de olor: emisión, difusión y desvanecimiento, y cuándo reaccionan a ellos.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PheromoneConfig {
+    /// Lado de cada celda de los campos.
+    pub cell_size: f32,
+    /// Rapidez con que cada celda se iguala con sus vecinas, por segundo.
+    pub diffusion: f32,
+    /// Fracción de la intensidad que se desvanece por segundo.
+    pub decay: f32,
+    /// Olor a comida por segundo por cada unidad de tamaño de planta.
+    pub food_emission: f32,
+    /// Olor a peligro por segundo de cada depredador.
+    pub danger_emission: f32,
+    /// Rastro por segundo de cada herbívoro.
+    pub trail_emission: f32,
+    /// Olor a comida mínimo para que un herbívoro hambriento siga su gradiente.
+    pub food_threshold: f32,
+    /// Olor a peligro a partir del cual un herbívoro huye sin ver al depredador.
+    pub danger_threshold: f32,
+}
+
+impl Default for PheromoneConfig {
+    fn default() -> Self {
+        Self {
+            cell_size: 20.0,
+            diffusion: 20.0,
+            decay: 0.25,
+            food_emission: 1.0,
+            danger_emission: 10.0,
+            trail_emission: 2.0,
+            food_threshold: 0.05,
+            danger_threshold: 0.5,
+        }
+    }
+}
+
+impl PheromoneConfig {
+    fn check(&self, section: &str, problems: &mut Vec<String>) {
+        if !(self.cell_size > 0.0 && self.cell_size.is_finite()) {
+            problems.push(format!(
+                "{section}.cell_size debe ser mayor que 0 (valor: {})",
+                self.cell_size
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.decay) {
+            problems.push(format!(
+                "{section}.decay debe estar entre 0 y 1 (valor: {})",
+                self.decay
+            ));
+        }
+        for (name, value) in [
+            ("diffusion", self.diffusion),
+            ("food_emission", self.food_emission),
+            ("danger_emission", self.danger_emission),
+            ("trail_emission", self.trail_emission),
+            ("food_threshold", self.food_threshold),
+            ("danger_threshold", self.danger_threshold),
+        ] {
+            if !(value >= 0.0 && value.is_finite()) {
+                problems.push(format!(
+                    "{section}.{name} no puede ser negativo (valor: {value})"
+                ));
+            }
+        }
+    }
+}
+
+/// Error al cargar o validar la configuración.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: ron::error::SpannedError,
+    },
+    Invalid {
+        path: Option<PathBuf>,
+        problems: Vec<String>,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "no se pudo leer '{}': {source}", path.display())
+            }
+            ConfigError::Parse { path, source } => write!(
+                f,
+                "error de sintaxis en '{}' (línea {}, columna {}): {}",
+                path.display(),
+                source.position.line,
+                source.position.col,
+                source.code
+            ),
+            ConfigError::Invalid { path, problems } => {
+                match path {
+                    Some(path) => write!(f, "configuración inválida en '{}':", path.display())?,
+                    None => write!(f, "configuración inválida:")?,
+                }
+                for problem in problems {
+                    write!(f, "\n  - {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            ConfigError::Parse { source, .. } => Some(source),
+            ConfigError::Invalid { .. } => None,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Lee, interpreta y valida un archivo de configuración RON.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let config: SimConfig = ron::from_str(&text).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        config.validate().map_err(|problems| ConfigError::Invalid {
+            path: Some(path.to_path_buf()),
+            problems,
+        })?;
+        Ok(config)
+    }
+
+    /// Comprueba que los valores tengan sentido; devuelve todos los problemas encontrados.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        let mut positive = |name: &str, value: f32| {
+            if !(value > 0.0 && value.is_finite()) {
+                problems.push(format!("{name} debe ser mayor que 0 (valor: {value})"));
+            }
+        };
+
+        let p = &self.population;
+        positive("population.spawn_half_width", p.spawn_half_width);
+        positive("population.spawn_half_height", p.spawn_half_height);
+
+        let c = &self.creatures;
+        positive("creatures.initial_energy", c.initial_energy);
+        positive("creatures.energy_drain", c.energy_drain);
+        positive("creatures.max_age", c.max_age);
+        positive("creatures.reproduction_energy", c.reproduction_energy);
+        positive("creatures.seek_speed_factor", c.seek_speed_factor);
+        positive("creatures.flee_speed_factor", c.flee_speed_factor);
+        positive("creatures.flee_radius", c.flee_radius);
+        positive("creatures.vision_range", c.vision_range);
+        positive("creatures.eat_radius", c.eat_radius);
+
+        let pr = &self.predators;
+        positive("predators.initial_energy", pr.initial_energy);
+        positive("predators.energy_drain", pr.energy_drain);
+        positive("predators.max_energy", pr.max_energy);
+        positive("predators.hunt_radius", pr.hunt_radius);
+        positive("predators.hunt_speed_factor", pr.hunt_speed_factor);
+        positive("predators.attack_radius", pr.attack_radius);
+        positive("predators.mate_search_radius", pr.mate_search_radius);
+        positive("predators.mate_radius", pr.mate_radius);
+
+        let pl = &self.plants;
+        positive("plants.initial_size", pl.initial_size);
+        positive("plants.max_size", pl.max_size);
+        positive("plants.reproduction_interval", pl.reproduction_interval);
+
+        positive("mating.search_radius", self.mating.search_radius);
+        positive("mating.mate_radius", self.mating.mate_radius);
+
+        positive(
+            "collisions.min_separation_distance",
+            self.collisions.min_separation_distance,
+        );
+        positive("spatial.cell_size", self.spatial.cell_size);
+
+        let non_negative = [
+            ("creatures.reproduction_cooldown", c.reproduction_cooldown),
+            ("creatures.reproduction_cost", c.reproduction_cost),
+            ("creatures.child_velocity_noise", c.child_velocity_noise),
+            ("creatures.size_drain", c.size_drain),
+            ("creatures.speed_drain", c.speed_drain),
+            ("creatures.perception_noise", c.perception_noise),
+            ("predators.grip_time_per_size", pr.grip_time_per_size),
+            ("predators.size_drain", pr.size_drain),
+            ("predators.speed_drain", pr.speed_drain),
+            ("predators.perception_noise", pr.perception_noise),
+            ("predators.prey_energy", pr.prey_energy),
+            ("predators.reproduction_cooldown", pr.reproduction_cooldown),
+            ("predators.reproduction_cost", pr.reproduction_cost),
+            ("plants.growth_rate", pl.growth_rate),
+            ("plants.seed_spread", pl.seed_spread),
+            ("plants.energy_value", pl.energy_value),
+            (
+                "collisions.separation_force",
+                self.collisions.separation_force,
+            ),
+        ];
+        for (name, value) in non_negative {
+            if !(value >= 0.0 && value.is_finite()) {
+                problems.push(format!("{name} no puede ser negativo (valor: {value})"));
+            }
+        }
+
+        let ranges = [("plants.lifespan", pl.lifespan)];
+        for (name, range) in ranges {
+            check_range(name, range, &mut problems);
+        }
+
+        for (name, degrees) in [
+            ("creatures.field_of_view", c.field_of_view),
+            ("predators.field_of_view", pr.field_of_view),
+        ] {
+            if !(degrees > 0.0 && degrees <= 360.0) {
+                problems.push(format!(
+                    "{name} debe estar entre 0 y 360 grados (valor: {degrees})"
+                ));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.mating.max_genetic_distance) {
+            problems.push(format!(
+                "mating.max_genetic_distance debe estar entre 0 y 1 (valor: {})",
+                self.mating.max_genetic_distance
+            ));
+        }
+
+        self.genome.check("genome", &mut problems);
+        if let Some(pheromones) = &self.pheromones {
+            pheromones.check("pheromones", &mut problems);
+        }
+        pr.genome.check("predators.genome", &mut problems);
+        if pr.genome.brain.is_some() {
+            problems.push(
+                "predators.genome.brain no está soportado: solo los herbívoros tienen cerebro"
+                    .to_string(),
+            );
+        }
+
+        if c.hunger_threshold >= c.reproduction_energy {
+            problems.push(format!(
+                "creatures.hunger_threshold ({}) debe ser menor que creatures.reproduction_energy ({})",
+                c.hunger_threshold, c.reproduction_energy
+            ));
+        }
+        // Solo ganan energía cazando, y solo cazan con hambre: una presa debe bastar
+        // para que un depredador hambriento entre en temporada.
+        let after_prey = (pr.hunger_threshold + pr.prey_energy).min(pr.max_energy);
+        if after_prey <= pr.season_energy {
+            problems.push(format!(
+                "predators.hunger_threshold ({}) más predators.prey_energy ({}), hasta predators.max_energy ({}), debe superar predators.season_energy ({}): si no, nunca entran en temporada",
+                pr.hunger_threshold, pr.prey_energy, pr.max_energy, pr.season_energy
+            ));
+        }
+        if pl.initial_size > pl.max_size {
+            problems.push(format!(
+                "plants.initial_size ({}) no puede superar plants.max_size ({})",
+                pl.initial_size, pl.max_size
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+fn check_range(name: &str, range: ValueRange, problems: &mut Vec<String>) {
+    if !(range.min >= 0.0 && range.min <= range.max && range.max.is_finite()) {
+        problems.push(format!(
+            "{name} debe cumplir 0 <= min <= max (min: {}, max: {})",
+            range.min, range.max
+        ));
+    }
+}
+
+/// Un parámetro cuyo valor difiere entre dos configuraciones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChange {
+    /// Ruta del campo, p. ej. `creatures.max_age`.
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} → {}", self.field, self.old, self.new)
+    }
+}
+
+impl SimConfig {
+    /// Lista los parámetros que cambian al pasar de `self` a `other`.
+    pub fn diff(&self, other: &SimConfig) -> Vec<ConfigChange> {
+        let old = self.flatten();
+        let new = other.flatten();
+
+        new.into_iter()
+            .filter_map(|(field, new)| {
+                let old = old.get(&field).cloned().unwrap_or_default();
+                (old != new).then_some(ConfigChange { field, old, new })
+            })
+            .collect()
+    }
+
+    /// Pares `ruta → valor` de todos los campos hoja de la configuración.
+    fn flatten(&self) -> BTreeMap<String, String> {
+        fn walk(prefix: &str, value: &ron::Value, out: &mut BTreeMap<String, String>) {
+            match value {
+                ron::Value::Map(map) => {
+                    for (key, value) in map.iter() {
+                        let key = match key {
+                            ron::Value::String(key) => key.clone(),
+                            other => ron::to_string(other).unwrap_or_default(),
+                        };
+                        let path = if prefix.is_empty() {
+                            key
+                        } else {
+                            format!("{prefix}.{key}")
+                        };
+                        walk(&path, value, out);
+                    }
+                }
+                ron::Value::Seq(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        walk(&format!("{prefix}[{i}]"), item, out);
+                    }
+                }
+                leaf => {
+                    out.insert(prefix.to_string(), ron::to_string(leaf).unwrap_or_default());
+                }
+            }
+        }
+
+        let mut out = BTreeMap::new();
+        let value = ron::to_string(self)
+            .ok()
+            .and_then(|text| ron::from_str::<ron::Value>(&text).ok());
+        if let Some(value) = value {
+            walk("", &value, &mut out);
+        }
+        out
+    }
+}
+
+/// Archivo de configuración vigilado para recargarlo en caliente.
+#[derive(Resource, Debug)]
+pub struct ConfigWatcher {
+    pub path: PathBuf,
+    last_modified: Option<SystemTime>,
+    poll: Timer,
+}
+
+impl ConfigWatcher {
+    /// Intervalo entre comprobaciones del archivo.
+    const POLL_INTERVAL_SECS: f32 = 1.0;
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let last_modified = modified_time(&path);
+        Self {
+            path,
+            last_modified,
+            poll: Timer::from_seconds(Self::POLL_INTERVAL_SECS, TimerMode::Repeating),
+        }
+    }
+
+    /// Avanza el temporizador y devuelve `true` si el archivo cambió desde la última comprobación.
+    pub fn poll_changed(&mut self, delta: Duration) -> bool {
+        if !self.poll.tick(delta).just_finished() {
+            return false;
+        }
+
+        let modified = modified_time(&self.path);
+        if modified.is_some() && modified != self.last_modified {
+            self.last_modified = modified;
+            return true;
+        }
+        false
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
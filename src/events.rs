@@ -0,0 +1,65 @@
+//! Eventos del ecosistema.
+//!
+//! Los sistemas de la simulación los emiten al ocurrir cada hecho; estadísticas,
+//! registros, exportadores o interfaces se suscriben con un `EventReader` sin tocar
+//! la lógica principal.
+
+use crate::components::{Genes, State};
+use crate::lineage::OrganismId;
+use crate::resources::{DeathCause, Species};
+use bevy::prelude::*;
+
+/// Ha nacido un organismo.
+#[derive(Event, Clone, Debug)]
+pub struct BirthEvent {
+    pub entity: Entity,
+    pub id: OrganismId,
+    pub species: Species,
+    /// Progenitores, en el orden en que se eligieron.
+    pub parents: Vec<Entity>,
+    pub generation: u32,
+    /// Genes del recién nacido, si su especie los tiene.
+    pub genes: Option<Genes>,
+}
+
+/// Un organismo ha muerto; se emite justo antes de despawnearlo.
+#[derive(Event, Clone, Debug)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub id: OrganismId,
+    pub species: Species,
+    pub cause: DeathCause,
+    pub age: f32,
+    pub generation: u32,
+    /// Genes del organismo, si su especie los tiene.
+    pub genes: Option<Genes>,
+}
+
+/// Un organismo ha comido: un herbívoro una planta o un depredador una presa.
+#[derive(Event, Clone, Debug)]
+pub struct FeedEvent {
+    pub eater: Entity,
+    pub species: Species,
+    /// Planta o presa consumida (ya despawneada al leer el evento).
+    pub food: Entity,
+    pub energy_gained: f32,
+}
+
+/// Un organismo ha cambiado de estado de comportamiento.
+#[derive(Event, Clone, Debug)]
+pub struct StateChangedEvent {
+    pub entity: Entity,
+    pub species: Species,
+    pub from: State,
+    pub to: State,
+}
+
+/// Los genes de un recién nacido tras heredar y mutar los de su progenitor.
+///
+/// Con reproducción sexual, `parent` es el cruce de ambos progenitores antes de mutar.
+#[derive(Event, Clone, Debug)]
+pub struct MutationEvent {
+    pub entity: Entity,
+    pub parent: Genes,
+    pub child: Genes,
+}
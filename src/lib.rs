@@ -0,0 +1,34 @@
+//! Simulador de vida artificial: ecosistema de herbívoros, depredadores y plantas.
+//!
+//! La biblioteca expone los componentes, recursos y sistemas del ecosistema junto con
+//! plugins listos para montar en cualquier [`App`](bevy::prelude::App):
+//!
+//! - [`SimulationPlugin`]: la ecología completa, sin dependencias de ventana.
+//! - [`RenderPlugin`]: cámara, sprites y sincronización de límites con la ventana.
+//! - [`HudPlugin`]: textos de estadísticas y FPS.
+//! - [`PhylogenyPlugin`]: panel con el árbol genealógico de la población.
+//! - [`InspectorPlugin`]: inspector de decisiones del organismo seleccionado.
+//! - [`PheromoneOverlayPlugin`]: capa con los campos de olor.
+//! - [`ControlsPlugin`]: pausa, velocidad e instantáneas desde el teclado.
+//! - [`ConfigHotReloadPlugin`]: recarga en caliente del archivo de configuración.
+//! - [`LineageExportPlugin`]: árbol genealógico en Newick o JSON al salir.
+//! - [`StatsExportPlugin`]: series temporales de la población en CSV o JSON Lines.
+
+pub mod brain;
+pub mod components;
+pub mod config;
+pub mod events;
+pub mod lineage;
+pub mod neat;
+pub mod plugins;
+pub mod resources;
+pub mod snapshot;
+pub mod stats_export;
+pub mod systems;
+pub mod utils;
+
+pub use plugins::{
+    ConfigHotReloadPlugin, ControlsPlugin, HudPlugin, InspectorPlugin, LineageExportPlugin,
+    PheromoneOverlayPlugin, PhylogenyPlugin, RenderPlugin, SimulationPlugin, SimulationSet,
+    StatsExportPlugin,
+};
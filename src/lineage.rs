@@ -0,0 +1,272 @@
+//! Registro genealógico de todos los organismos de la simulación.
+
+use crate::components::Genes;
+use crate::resources::{DeathCause, Species};
+use crate::snapshot::GenesSnapshot;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{fmt, fs, io};
+
+/// Identificador estable de un organismo: único durante toda la simulación y
+/// conservado en las instantáneas, a diferencia de `Entity`.
+pub type OrganismId = u64;
+
+/// Datos genealógicos de un organismo.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LineageRecord {
+    pub id: OrganismId,
+    pub species: Species,
+    /// Vacío para los fundadores de la población inicial.
+    pub parents: Vec<OrganismId>,
+    pub generation: u32,
+    /// Tiempo de simulación del nacimiento.
+    pub born_at: f32,
+    pub died_at: Option<f32>,
+    pub death_cause: Option<DeathCause>,
+    /// Genes al nacer, si su especie los tiene.
+    #[serde(default)]
+    pub genes: Option<GenesSnapshot>,
+}
+
+impl LineageRecord {
+    pub fn is_alive(&self) -> bool {
+        self.died_at.is_none()
+    }
+}
+
+/// Árbol genealógico completo: un registro por organismo nacido.
+///
+/// Los identificadores se asignan en orden de nacimiento, así que un id mayor
+/// siempre corresponde a un organismo más reciente.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LineageStore {
+    next_id: OrganismId,
+    records: BTreeMap<OrganismId, LineageRecord>,
+}
+
+impl LineageStore {
+    /// Reserva un id para un organismo que acaba de nacer y guarda su registro.
+    pub fn register(
+        &mut self,
+        species: Species,
+        parents: Vec<OrganismId>,
+        generation: u32,
+        born_at: f32,
+        genes: Option<&Genes>,
+    ) -> OrganismId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.records.insert(
+            id,
+            LineageRecord {
+                id,
+                species,
+                parents,
+                generation,
+                born_at,
+                died_at: None,
+                death_cause: None,
+                genes: genes.map(GenesSnapshot::from),
+            },
+        );
+        id
+    }
+
+    pub fn record_death(&mut self, id: OrganismId, time: f32, cause: DeathCause) {
+        if let Some(record) = self.records.get_mut(&id) {
+            record.died_at = Some(time);
+            record.death_cause = Some(cause);
+        }
+    }
+
+    pub fn get(&self, id: OrganismId) -> Option<&LineageRecord> {
+        self.records.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &LineageRecord> {
+        self.records.values()
+    }
+
+    pub fn living(&self) -> impl Iterator<Item = &LineageRecord> {
+        self.records.values().filter(|record| record.is_alive())
+    }
+
+    /// Todos los antepasados de `id`, del más reciente al más antiguo y sin repetir.
+    pub fn ancestry(&self, id: OrganismId) -> Vec<OrganismId> {
+        let mut ancestors: Vec<OrganismId> = self
+            .ancestors_inclusive(id)
+            .into_iter()
+            .filter(|ancestor| *ancestor != id)
+            .collect();
+        ancestors.sort_unstable_by(|a, b| b.cmp(a));
+        ancestors
+    }
+
+    /// Fundadores con descendencia viva (incluidos ellos mismos) y cuántos
+    /// descendientes vivos tiene cada uno.
+    pub fn surviving_lineages(&self, species: Species) -> BTreeMap<OrganismId, usize> {
+        let mut lineages = BTreeMap::new();
+        for record in self.living().filter(|record| record.species == species) {
+            for ancestor in self.ancestors_inclusive(record.id) {
+                if self
+                    .records
+                    .get(&ancestor)
+                    .is_some_and(|a| a.parents.is_empty())
+                {
+                    *lineages.entry(ancestor).or_insert(0) += 1;
+                }
+            }
+        }
+        lineages
+    }
+
+    /// Ancestro común más reciente de todos los organismos dados (que puede ser
+    /// uno de ellos). `None` si no comparten ningún ancestro registrado.
+    pub fn most_recent_common_ancestor(
+        &self,
+        ids: impl IntoIterator<Item = OrganismId>,
+    ) -> Option<OrganismId> {
+        let mut ids = ids.into_iter();
+        let mut common = self.ancestors_inclusive(ids.next()?);
+        for id in ids {
+            let ancestors = self.ancestors_inclusive(id);
+            common.retain(|ancestor| ancestors.contains(ancestor));
+        }
+        common.into_iter().max()
+    }
+
+    /// Conjunto formado por `id` y todos sus antepasados registrados.
+    fn ancestors_inclusive(&self, id: OrganismId) -> HashSet<OrganismId> {
+        let mut visited = HashSet::default();
+        let mut pending = vec![id];
+        while let Some(current) = pending.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(record) = self.records.get(&current) {
+                pending.extend(record.parents.iter().copied());
+            }
+        }
+        visited
+    }
+
+    /// Árbol en formato Newick, con un nodo raíz anónimo que agrupa a los fundadores.
+    ///
+    /// Cada nodo se etiqueta con su id y la longitud de rama es el tiempo entre su
+    /// nacimiento y el de su progenitor. Con reproducción sexual un organismo solo
+    /// cuelga de su primer progenitor, porque Newick no admite reticulaciones.
+    pub fn to_newick(&self) -> String {
+        let mut children: HashMap<OrganismId, Vec<OrganismId>> = HashMap::default();
+        let mut roots = Vec::new();
+        for record in self.records.values() {
+            match record.parents.first() {
+                Some(parent) if self.records.contains_key(parent) => {
+                    children.entry(*parent).or_default().push(record.id);
+                }
+                _ => roots.push(record.id),
+            }
+        }
+
+        // Pila explícita: una sola línea puede tener decenas de miles de generaciones.
+        let mut newick = String::from("(");
+        let mut pending = Vec::new();
+        push_newick_siblings(&mut pending, &roots);
+        while let Some(step) = pending.pop() {
+            match step {
+                NewickStep::Node(id) => match children.get(&id) {
+                    Some(kids) => {
+                        pending.push(NewickStep::Label(id));
+                        pending.push(NewickStep::Text(')'));
+                        push_newick_siblings(&mut pending, kids);
+                        pending.push(NewickStep::Text('('));
+                    }
+                    None => self.write_newick_label(&mut newick, id),
+                },
+                NewickStep::Label(id) => self.write_newick_label(&mut newick, id),
+                NewickStep::Text(c) => newick.push(c),
+            }
+        }
+        newick.push_str(");");
+        newick
+    }
+
+    fn write_newick_label(&self, out: &mut String, id: OrganismId) {
+        let record = &self.records[&id];
+        let parent_birth = record
+            .parents
+            .first()
+            .and_then(|parent| self.records.get(parent))
+            .map_or(0.0, |parent| parent.born_at);
+        let _ = write!(out, "{id}:{:.3}", record.born_at - parent_birth);
+    }
+
+    /// Registros en JSON, ordenados por id.
+    pub fn to_json(&self) -> String {
+        let records: Vec<&LineageRecord> = self.records.values().collect();
+        serde_json::to_string_pretty(&records).unwrap_or_default()
+    }
+
+    /// Escribe el árbol en `path`: Newick si la extensión es `.nwk`/`.newick`, JSON en otro caso.
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<(), LineageExportError> {
+        let path = path.as_ref();
+        let text = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("nwk" | "newick") => self.to_newick(),
+            _ => self.to_json(),
+        };
+        fs::write(path, text).map_err(|source| LineageExportError {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Paso pendiente al escribir el árbol Newick.
+enum NewickStep {
+    /// Un nodo con su subárbol.
+    Node(OrganismId),
+    /// La etiqueta de un nodo, tras cerrar la lista de sus hijos.
+    Label(OrganismId),
+    Text(char),
+}
+
+/// Apila `ids` separados por comas para que salgan en orden.
+fn push_newick_siblings(pending: &mut Vec<NewickStep>, ids: &[OrganismId]) {
+    for (i, id) in ids.iter().enumerate().rev() {
+        pending.push(NewickStep::Node(*id));
+        if i > 0 {
+            pending.push(NewickStep::Text(','));
+        }
+    }
+}
+
+/// Error al escribir el árbol genealógico.
+#[derive(Debug)]
+pub struct LineageExportError {
+    pub path: std::path::PathBuf,
+    pub source: io::Error,
+}
+
+impl fmt::Display for LineageExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no se pudo escribir el árbol genealógico en '{}': {}",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for LineageExportError {}
@@ -1,53 +1,157 @@
-mod components;
-mod resources;
-mod systems;
-mod utils;
-
-use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, log::LogPlugin, prelude::*};
-use resources::Stats;
-
-fn main() {
-    App::new()
-        .insert_resource(Stats::default())
-        .add_plugins((
-            DefaultPlugins.set(LogPlugin {
-                level: bevy::log::Level::INFO,
-                filter: "wgpu=error,bevy_render=info".into(),
-                custom_layer: |_| None,
-            }),
-            FrameTimeDiagnosticsPlugin::default(),
+mod cli;
+
+use std::time::Duration;
+
+use bevy::{
+    app::ScheduleRunnerPlugin, log::LogPlugin, prelude::*, time::TimeUpdateStrategy,
+    window::WindowResolution,
+};
+use cli::CliArgs;
+use simulador_evolutivo::{
+    ConfigHotReloadPlugin, ControlsPlugin, HudPlugin, InspectorPlugin, LineageExportPlugin,
+    PheromoneOverlayPlugin, PhylogenyPlugin, RenderPlugin, SimulationPlugin, StatsExportPlugin,
+    config::SimConfig,
+    plugins::DEFAULT_TICK_RATE,
+    resources::{RunDuration, SimRng, SnapshotSettings, WorldBounds},
+    snapshot::WorldSnapshot,
+    stats_export::{DEFAULT_STATS_INTERVAL, StatsRecorder},
+    systems,
+};
+
+fn main() -> AppExit {
+    let args = match CliArgs::from_env() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return AppExit::error();
+        }
+    };
+
+    let config = match &args.config {
+        Some(path) => match SimConfig::load(path) {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("❌ {error}");
+                return AppExit::error();
+            }
+        },
+        None => SimConfig::default(),
+    };
+
+    let log_plugin = LogPlugin {
+        level: bevy::log::Level::INFO,
+        filter: "wgpu=error,bevy_render=info".into(),
+        custom_layer: |_| None,
+    };
+
+    let tick_rate = args.tick_rate.unwrap_or(DEFAULT_TICK_RATE);
+
+    let snapshot = match &args.load {
+        Some(path) => match WorldSnapshot::load(path) {
+            Ok(snapshot) => Some(snapshot),
+            Err(error) => {
+                eprintln!("❌ {error}");
+                return AppExit::error();
+            }
+        },
+        None => None,
+    };
+
+    // Una instantánea continúa en el mundo en que se guardó.
+    let saved_size = snapshot.as_ref().map(|snapshot| snapshot.bounds.size());
+    if let (Some(saved), Some(requested)) = (saved_size, args.world_size)
+        && saved != requested
+    {
+        eprintln!(
+            "❌ La instantánea se guardó con un mundo de {}x{}; no se puede continuar con --world-size {}x{}",
+            saved.x, saved.y, requested.x, requested.y
+        );
+        return AppExit::error();
+    }
+    let world_size = saved_size
+        .or(args.world_size)
+        .unwrap_or(WorldBounds::default().size());
+
+    let mut app = App::new();
+    app.insert_resource(config)
+        .insert_resource(WorldBounds::from_size(world_size));
+
+    if let Some(seed) = args.seed {
+        app.insert_resource(SimRng::from_seed(seed));
+    }
+
+    if let Some(duration) = args.duration {
+        app.insert_resource(RunDuration(duration));
+    }
+
+    if args.headless {
+        // Sin ventana no hay que esperar a nadie: cada iteración avanza exactamente
+        // un tick, tan rápido como permita la CPU.
+        app.add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+            log_plugin,
         ))
-        .add_systems(Startup, systems::setup)
-        .add_systems(
-            Update,
-            (
-                // Movimiento y colisiones
-                systems::move_entities,
-                systems::avoid_entity_overlap_system,
-                systems::boundary_bounce_system,
-
-                // IA y comportamiento
-                systems::seek_food_system,
-                systems::update_states,
-                systems::avoid_predators_system,
-
-                // Plantas y alimentación
-                systems::plant_growth_and_reproduction_system,
-                systems::herbivore_plant_collision_system,
-
-                // Depredadores
-                systems::predator_hunting_system,
-                systems::update_predator_cooldowns,
-                systems::update_predator_states,
-
-                // Reproducción
-                systems::reproduction_system,
-                systems::predator_reproduction_system,
-
-                // HUD
-                systems::update_hud,
-                systems::update_fps,
-            ),
-        )
-        .run();
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / tick_rate,
+        )))
+        .add_systems(Update, systems::log_headless_progress);
+    } else {
+        app.add_plugins((
+            DefaultPlugins.set(log_plugin).set(WindowPlugin {
+                // Los límites se sincronizan con la ventana, así que nace con el tamaño del mundo.
+                primary_window: Some(Window {
+                    resolution: WindowResolution::new(world_size.x, world_size.y),
+                    ..default()
+                }),
+                ..default()
+            }),
+            RenderPlugin,
+            HudPlugin,
+            PhylogenyPlugin,
+            InspectorPlugin,
+            PheromoneOverlayPlugin,
+            ControlsPlugin,
+        ));
+    }
+
+    if let Some(path) = args.config {
+        app.add_plugins(ConfigHotReloadPlugin { path });
+    }
+
+    if let Some(path) = &args.save {
+        app.insert_resource(SnapshotSettings {
+            path: path.clone(),
+            save_on_exit: true,
+        });
+    }
+
+    if let Some(path) = &args.stats_out {
+        let interval = args.stats_interval.unwrap_or(DEFAULT_STATS_INTERVAL);
+        match StatsRecorder::create(path, interval) {
+            Ok(recorder) => {
+                app.insert_resource(recorder).add_plugins(StatsExportPlugin);
+            }
+            Err(error) => {
+                eprintln!("❌ No se pudo crear '{}': {error}", path.display());
+                return AppExit::error();
+            }
+        }
+    }
+
+    if let Some(path) = args.lineage_out {
+        app.add_plugins(LineageExportPlugin { path });
+    }
+
+    app.add_plugins(SimulationPlugin {
+        tick_rate,
+        // Al continuar una instantánea la población sale del archivo.
+        spawn_initial_population: args.load.is_none(),
+    });
+
+    if let (Some(path), Some(snapshot)) = (&args.load, snapshot) {
+        info!("📂 Continuando la simulación desde '{}'", path.display());
+        snapshot.restore(app.world_mut());
+    }
+
+    app.run()
 }
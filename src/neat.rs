@@ -0,0 +1,539 @@
+//! Cerebros de topología evolutiva al estilo NEAT.
+//!
+//! Cada genoma es una lista de neuronas y de conexiones. Las mutaciones pueden
+//! añadir conexiones o partir una conexión en dos con una neurona nueva, y cada
+//! cambio estructural recibe un número de innovación global: dos mutaciones iguales
+//! en organismos distintos comparten número, lo que permite alinear genomas al
+//! cruzarlos y medir su distancia para agruparlos en especies.
+
+use crate::brain::{Brain, INPUTS, OUTPUTS};
+use crate::config::{BrainConfig, NeatConfig};
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Identificador de neurona: las entradas ocupan `0..INPUTS`, el sesgo `INPUTS` y
+/// las salidas las siguientes `OUTPUTS`; las ocultas se numeran a partir de ahí.
+pub type NodeId = u32;
+pub type Innovation = u64;
+
+const BIAS: NodeId = INPUTS as NodeId;
+const FIRST_OUTPUT: NodeId = BIAS + 1;
+const FIRST_HIDDEN: NodeId = FIRST_OUTPUT + OUTPUTS as NodeId;
+/// Intentos para encontrar un par de neuronas que se pueda conectar.
+const ADD_CONNECTION_ATTEMPTS: usize = 20;
+/// Probabilidad de que un gen desactivado en algún progenitor siga desactivado en la cría.
+const KEEP_DISABLED: f64 = 0.75;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeKind {
+    Input,
+    Bias,
+    Output,
+    Hidden,
+}
+
+fn kind_of(node: NodeId) -> NodeKind {
+    match node {
+        n if n < BIAS => NodeKind::Input,
+        BIAS => NodeKind::Bias,
+        n if n < FIRST_HIDDEN => NodeKind::Output,
+        _ => NodeKind::Hidden,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionGene {
+    pub innovation: Innovation,
+    pub from: NodeId,
+    pub to: NodeId,
+    pub weight: f32,
+    pub enabled: bool,
+}
+
+/// Genoma NEAT: neuronas ocultas y conexiones ordenadas por innovación.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeatGenome {
+    /// Neuronas ocultas; las de entrada, sesgo y salida existen siempre.
+    pub hidden: BTreeSet<NodeId>,
+    pub connections: Vec<ConnectionGene>,
+    /// Especie asignada al nacer (véase [`Speciation`]); no se hereda.
+    #[serde(default)]
+    pub species: Option<u32>,
+}
+
+impl NeatGenome {
+    /// Genoma inicial: cada entrada y el sesgo conectados con cada salida.
+    pub fn minimal(
+        brain: &BrainConfig,
+        innovations: &mut InnovationTracker,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let w = brain.initial_weight;
+        let mut connections = Vec::with_capacity((INPUTS + 1) * OUTPUTS);
+        for from in 0..=BIAS {
+            for to in FIRST_OUTPUT..FIRST_HIDDEN {
+                connections.push(ConnectionGene {
+                    innovation: innovations.connection(from, to),
+                    from,
+                    to,
+                    weight: rng.random_range(-w..=w),
+                    enabled: true,
+                });
+            }
+        }
+        connections.sort_unstable_by_key(|c| c.innovation);
+        Self {
+            hidden: BTreeSet::new(),
+            connections,
+            species: None,
+        }
+    }
+
+    /// Conexiones activas.
+    pub fn enabled_connections(&self) -> usize {
+        self.connections.iter().filter(|c| c.enabled).count()
+    }
+
+    /// Propaga las entradas en orden topológico; las neuronas sin entradas valen 0.
+    pub fn activate(&self, inputs: &[f32; INPUTS]) -> [f32; OUTPUTS] {
+        let mut values: BTreeMap<NodeId, f32> = BTreeMap::new();
+        for (node, value) in inputs.iter().enumerate() {
+            values.insert(node as NodeId, *value);
+        }
+        values.insert(BIAS, 1.0);
+
+        let enabled: Vec<&ConnectionGene> = self.connections.iter().filter(|c| c.enabled).collect();
+        let mut pending: BTreeMap<NodeId, usize> = BTreeMap::new();
+        for c in &enabled {
+            *pending.entry(c.to).or_default() += 1;
+        }
+        let mut sums: BTreeMap<NodeId, f32> = BTreeMap::new();
+        // Una neurona que perdió todas sus entradas activas (al cruzar o desactivar
+        // genes) vale 0, pero debe propagarse igualmente para no dejar bloqueadas a
+        // las que dependen de ella. Van al fondo de la pila, tras las entradas.
+        let mut ready: Vec<NodeId> = Vec::new();
+        for c in &enabled {
+            if c.from > BIAS && !pending.contains_key(&c.from) && !ready.contains(&c.from) {
+                values.insert(c.from, 0.0);
+                ready.push(c.from);
+            }
+        }
+        ready.extend(0..=BIAS);
+        while let Some(node) = ready.pop() {
+            let value = values[&node];
+            for c in enabled.iter().filter(|c| c.from == node) {
+                *sums.entry(c.to).or_default() += c.weight * value;
+                let left = pending.entry(c.to).or_default();
+                *left -= 1;
+                if *left == 0 {
+                    values.insert(c.to, sums[&c.to].tanh());
+                    ready.push(c.to);
+                }
+            }
+        }
+
+        let mut outputs = [0.0; OUTPUTS];
+        for (i, output) in outputs.iter_mut().enumerate() {
+            *output = values
+                .get(&(FIRST_OUTPUT + i as NodeId))
+                .copied()
+                .unwrap_or(0.0);
+        }
+        outputs
+    }
+
+    /// Copia mutada: perturba pesos y, con sus probabilidades, añade una conexión
+    /// o parte una conexión con una neurona nueva.
+    pub fn mutate(
+        &self,
+        brain: &BrainConfig,
+        neat: &NeatConfig,
+        innovations: &mut InnovationTracker,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut child = Self {
+            species: None,
+            ..self.clone()
+        };
+        let bound = brain.weight_bound;
+        for c in &mut child.connections {
+            if rng.random::<f32>() < brain.weight_mutation_rate {
+                c.weight = (c.weight + brain.weight_mutation.sample(rng)).clamp(-bound, bound);
+            }
+        }
+        if rng.random::<f32>() < neat.add_connection_rate {
+            child.add_connection(brain, innovations, rng);
+        }
+        if rng.random::<f32>() < neat.add_node_rate {
+            child.add_node(innovations, rng);
+        }
+        child
+    }
+
+    fn add_connection(
+        &mut self,
+        brain: &BrainConfig,
+        innovations: &mut InnovationTracker,
+        rng: &mut impl Rng,
+    ) {
+        let sources: Vec<NodeId> = (0..=BIAS).chain(self.hidden.iter().copied()).collect();
+        let targets: Vec<NodeId> = (FIRST_OUTPUT..FIRST_HIDDEN)
+            .chain(self.hidden.iter().copied())
+            .collect();
+        for _ in 0..ADD_CONNECTION_ATTEMPTS {
+            let from = sources[rng.random_range(0..sources.len())];
+            let to = targets[rng.random_range(0..targets.len())];
+            if from == to
+                || self
+                    .connections
+                    .iter()
+                    .any(|c| c.from == from && c.to == to)
+                || self.reaches(to, from)
+            {
+                continue;
+            }
+            let w = brain.initial_weight;
+            self.insert_connection(ConnectionGene {
+                innovation: innovations.connection(from, to),
+                from,
+                to,
+                weight: rng.random_range(-w..=w),
+                enabled: true,
+            });
+            return;
+        }
+    }
+
+    fn add_node(&mut self, innovations: &mut InnovationTracker, rng: &mut impl Rng) {
+        let enabled: Vec<usize> = (0..self.connections.len())
+            .filter(|&i| self.connections[i].enabled)
+            .collect();
+        if enabled.is_empty() {
+            return;
+        }
+        let index = enabled[rng.random_range(0..enabled.len())];
+        let split = self.connections[index].clone();
+        let node = innovations.split(split.innovation);
+        if !self.hidden.insert(node) {
+            return;
+        }
+        self.connections[index].enabled = false;
+        // La entrada a la neurona nueva pesa 1 y la salida conserva el peso
+        // original, así la red se comporta casi igual que antes de mutar.
+        self.insert_connection(ConnectionGene {
+            innovation: innovations.connection(split.from, node),
+            from: split.from,
+            to: node,
+            weight: 1.0,
+            enabled: true,
+        });
+        self.insert_connection(ConnectionGene {
+            innovation: innovations.connection(node, split.to),
+            from: node,
+            to: split.to,
+            weight: split.weight,
+            enabled: true,
+        });
+    }
+
+    fn insert_connection(&mut self, gene: ConnectionGene) {
+        let at = self
+            .connections
+            .partition_point(|c| c.innovation < gene.innovation);
+        self.connections.insert(at, gene);
+    }
+
+    /// Indica si hay un camino de `from` a `to`, contando también las conexiones
+    /// desactivadas para no crear ciclos si se reactivan al cruzar.
+    fn reaches(&self, from: NodeId, to: NodeId) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![from];
+        while let Some(node) = pending.pop() {
+            if node == to {
+                return true;
+            }
+            if visited.insert(node) {
+                pending.extend(
+                    self.connections
+                        .iter()
+                        .filter(|c| c.from == node)
+                        .map(|c| c.to),
+                );
+            }
+        }
+        false
+    }
+
+    /// Cruce NEAT: los genes comunes se toman al azar de cualquiera de los dos y
+    /// los disjuntos y sobrantes de `fitter`, el progenitor con más energía.
+    pub fn crossover(fitter: &Self, other: &Self, rng: &mut impl Rng) -> Self {
+        let others: BTreeMap<Innovation, &ConnectionGene> = other
+            .connections
+            .iter()
+            .map(|c| (c.innovation, c))
+            .collect();
+        let connections = fitter
+            .connections
+            .iter()
+            .map(|gene| match others.get(&gene.innovation) {
+                Some(matching) => {
+                    let mut child = if rng.random_bool(0.5) {
+                        gene.clone()
+                    } else {
+                        (*matching).clone()
+                    };
+                    child.enabled =
+                        (gene.enabled && matching.enabled) || !rng.random_bool(KEEP_DISABLED);
+                    child
+                }
+                None => gene.clone(),
+            })
+            .collect();
+        Self {
+            hidden: fitter.hidden.clone(),
+            connections,
+            species: None,
+        }
+    }
+
+    /// Distancia de compatibilidad: `(c1·E + c2·D) / N + c3·W̄`, con E genes
+    /// sobrantes, D disjuntos, N el tamaño del genoma mayor y W̄ la diferencia media
+    /// de peso de los genes comunes.
+    pub fn distance(&self, other: &Self, neat: &NeatConfig) -> f32 {
+        let (a, b) = (&self.connections, &other.connections);
+        let max_a = a.last().map_or(0, |c| c.innovation);
+        let max_b = b.last().map_or(0, |c| c.innovation);
+        let (mut i, mut j) = (0, 0);
+        let (mut excess, mut disjoint, mut matching, mut weight_diff) = (0, 0, 0, 0.0);
+        while i < a.len() || j < b.len() {
+            match (a.get(i), b.get(j)) {
+                (Some(x), Some(y)) if x.innovation == y.innovation => {
+                    matching += 1;
+                    weight_diff += (x.weight - y.weight).abs();
+                    i += 1;
+                    j += 1;
+                }
+                (Some(x), Some(y)) if x.innovation < y.innovation => {
+                    disjoint += 1;
+                    i += 1;
+                }
+                (Some(_), Some(_)) => {
+                    disjoint += 1;
+                    j += 1;
+                }
+                (Some(x), None) => {
+                    if x.innovation > max_b {
+                        excess += 1
+                    } else {
+                        disjoint += 1
+                    }
+                    i += 1;
+                }
+                (None, Some(y)) => {
+                    if y.innovation > max_a {
+                        excess += 1
+                    } else {
+                        disjoint += 1
+                    }
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+        let n = a.len().max(b.len()).max(1) as f32;
+        let mean_weight_diff = if matching > 0 {
+            weight_diff / matching as f32
+        } else {
+            0.0
+        };
+        neat.excess_coefficient * excess as f32 / n
+            + neat.disjoint_coefficient * disjoint as f32 / n
+            + neat.weight_coefficient * mean_weight_diff
+    }
+
+    /// Tipo de una neurona según su identificador.
+    pub fn node_kind(node: NodeId) -> NodeKind {
+        kind_of(node)
+    }
+}
+
+/// Historial global de innovaciones: la misma conexión o la misma división de una
+/// conexión recibe siempre el mismo número, aparezca en el organismo que aparezca.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnovationTracker {
+    next_innovation: Innovation,
+    next_node: NodeId,
+    connections: BTreeMap<(NodeId, NodeId), Innovation>,
+    splits: BTreeMap<Innovation, NodeId>,
+}
+
+impl Default for InnovationTracker {
+    fn default() -> Self {
+        Self {
+            next_innovation: 0,
+            next_node: FIRST_HIDDEN,
+            connections: BTreeMap::new(),
+            splits: BTreeMap::new(),
+        }
+    }
+}
+
+impl InnovationTracker {
+    /// Innovación de la conexión `from → to`, creándola si es la primera vez.
+    pub fn connection(&mut self, from: NodeId, to: NodeId) -> Innovation {
+        *self.connections.entry((from, to)).or_insert_with(|| {
+            self.next_innovation += 1;
+            self.next_innovation
+        })
+    }
+
+    /// Neurona que aparece al partir la conexión `innovation`.
+    pub fn split(&mut self, innovation: Innovation) -> NodeId {
+        *self.splits.entry(innovation).or_insert_with(|| {
+            let node = self.next_node;
+            self.next_node += 1;
+            node
+        })
+    }
+
+    pub fn innovations(&self) -> Innovation {
+        self.next_innovation
+    }
+}
+
+/// Especie de cerebros NEAT: genomas a menos de `compatibility_threshold` de su representante.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeciesRecord {
+    /// Genoma del fundador de la especie, con el que se comparan los recién nacidos.
+    pub representative: NeatGenome,
+    /// Miembros vivos.
+    pub members: usize,
+}
+
+/// Agrupación en especies de los cerebros NEAT de la población viva.
+///
+/// Cada recién nacido se une a la primera especie cuyo representante esté lo
+/// bastante cerca o funda una nueva; las especies sin miembros vivos desaparecen.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Speciation {
+    next_id: u32,
+    species: BTreeMap<u32, SpeciesRecord>,
+}
+
+impl Speciation {
+    /// Especie para `genome`, creando una nueva si no encaja en ninguna.
+    pub fn assign(&mut self, genome: &NeatGenome, neat: &NeatConfig) -> u32 {
+        let found = self.species.iter_mut().find(|(_, record)| {
+            record.representative.distance(genome, neat) < neat.compatibility_threshold
+        });
+        if let Some((id, record)) = found {
+            record.members += 1;
+            return *id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.species.insert(
+            id,
+            SpeciesRecord {
+                representative: genome.clone(),
+                members: 1,
+            },
+        );
+        info!(
+            "🧠 Nueva especie NEAT #{id}: {} neuronas ocultas, {} conexiones activas",
+            genome.hidden.len(),
+            genome.enabled_connections()
+        );
+        id
+    }
+
+    /// Actualiza los miembros vivos de cada especie y elimina las extinguidas.
+    pub fn recount(&mut self, members: &BTreeMap<u32, usize>) {
+        self.species.retain(|id, record| {
+            record.members = members.get(id).copied().unwrap_or(0);
+            if record.members == 0 {
+                info!("💀 Especie NEAT #{id} extinguida");
+            }
+            record.members > 0
+        });
+    }
+
+    /// Especies con miembros vivos.
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&SpeciesRecord> {
+        self.species.get(&id)
+    }
+
+    /// Total de especies surgidas desde el inicio.
+    pub fn total_created(&self) -> u32 {
+        self.next_id
+    }
+}
+
+/// Estado global de la evolución NEAT: historial de innovaciones y especies.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NeatState {
+    pub innovations: InnovationTracker,
+    pub species: Speciation,
+}
+
+impl NeatState {
+    /// Asigna especie al cerebro NEAT de un recién nacido; el resto de genes no cambia.
+    pub fn speciate(&mut self, brain: Option<&mut Brain>, config: Option<&BrainConfig>) {
+        if let (Some(Brain::Neat(genome)), Some(neat)) =
+            (brain, config.and_then(|brain| brain.neat.as_ref()))
+        {
+            genome.species = Some(self.species.assign(genome, neat));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gene(
+        innovation: Innovation,
+        from: NodeId,
+        to: NodeId,
+        weight: f32,
+        enabled: bool,
+    ) -> ConnectionGene {
+        ConnectionGene {
+            innovation,
+            from,
+            to,
+            weight,
+            enabled,
+        }
+    }
+
+    #[test]
+    fn orphaned_hidden_node_counts_as_zero() {
+        // La única entrada de la neurona oculta está desactivada; la salida debe
+        // recibir su 0 y el sesgo en lugar de quedarse sin calcular.
+        let hidden = FIRST_HIDDEN;
+        let genome = NeatGenome {
+            hidden: BTreeSet::from([hidden]),
+            connections: vec![
+                gene(0, 0, hidden, 1.0, false),
+                gene(1, hidden, FIRST_OUTPUT, 1.0, true),
+                gene(2, BIAS, FIRST_OUTPUT, 0.5, true),
+            ],
+            species: None,
+        };
+
+        let outputs = genome.activate(&[1.0; INPUTS]);
+
+        assert!((outputs[0] - 0.5f32.tanh()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::config::{ConfigWatcher, SimConfig};
+use crate::events::{BirthEvent, DeathEvent, FeedEvent, MutationEvent, StateChangedEvent};
+use crate::lineage::LineageStore;
+use crate::neat::NeatState;
+use crate::resources::{
+    LineageExportSettings, PheromoneField, SelectedOrganism, SimRng, SimulationSpeed,
+    SnapshotSettings, SpatialIndex, Stats, WorldBounds,
+};
+use crate::stats_export::StatsRecorder;
+use crate::systems;
+use bevy::{
+    app::RunFixedMainLoopSystem, diagnostic::FrameTimeDiagnosticsPlugin, prelude::*,
+    time::TimeSystem,
+};
+use std::path::PathBuf;
+
+/// Frecuencia por defecto del paso fijo de simulación (ticks por segundo).
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Fases de la simulación, ejecutadas en este orden dentro de `FixedUpdate`.
+///
+/// Los sistemas de cada fase también se encadenan para que el orden de ejecución,
+/// y por tanto el consumo del [`SimRng`], sea siempre el mismo.
+///
+/// Las herramientas externas pueden usar estas fases para insertar sistemas propios
+/// antes o después de cada una.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    /// Movimiento, límites del mundo, índice espacial y separación.
+    Movement,
+    /// Campos de olor, percepción, IA y cambios de estado.
+    Behaviour,
+    /// Plantas y alimentación.
+    Plants,
+    /// Caza y estado de los depredadores.
+    Predators,
+    /// Nacimientos.
+    Reproduction,
+    /// Recuento de nacimientos y muertes, tiempo de simulación y fin de ejecución.
+    Bookkeeping,
+}
+
+/// Ecología completa del simulador: recursos, población inicial y sistemas.
+///
+/// No requiere ventana, por lo que funciona tanto con `DefaultPlugins` como con `MinimalPlugins`.
+/// Los sistemas corren en `FixedUpdate`, así que el resultado no depende de los FPS.
+pub struct SimulationPlugin {
+    /// Si es `false` no se spawnea la población inicial (útil para pruebas y experimentos).
+    pub spawn_initial_population: bool,
+    /// Ticks de simulación por segundo simulado.
+    pub tick_rate: f64,
+}
+
+impl Default for SimulationPlugin {
+    fn default() -> Self {
+        Self {
+            spawn_initial_population: true,
+            tick_rate: DEFAULT_TICK_RATE,
+        }
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stats>()
+            .init_resource::<WorldBounds>()
+            .init_resource::<SimRng>()
+            .init_resource::<SimConfig>()
+            .init_resource::<SpatialIndex>()
+            .init_resource::<SimulationSpeed>()
+            .init_resource::<SnapshotSettings>()
+            .init_resource::<LineageStore>()
+            .init_resource::<NeatState>()
+            .init_resource::<PheromoneField>()
+            .add_event::<systems::SnapshotRequest>()
+            .add_event::<BirthEvent>()
+            .add_event::<DeathEvent>()
+            .add_event::<FeedEvent>()
+            .add_event::<StateChangedEvent>()
+            .add_event::<MutationEvent>()
+            .insert_resource(Time::<Fixed>::from_hz(self.tick_rate))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationSet::Movement,
+                    SimulationSet::Behaviour,
+                    SimulationSet::Plants,
+                    SimulationSet::Predators,
+                    SimulationSet::Reproduction,
+                    SimulationSet::Bookkeeping,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        systems::move_entities,
+                        systems::boundary_bounce_system,
+                        systems::rebuild_spatial_index,
+                        systems::avoid_entity_overlap_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Movement),
+                    (
+                        systems::update_pheromones,
+                        systems::perception_system,
+                        systems::seek_food_system,
+                        systems::update_states,
+                        systems::brain_system,
+                        systems::log_state_changes,
+                        systems::seek_mate_system.run_if(systems::sexual_reproduction_enabled),
+                        systems::avoid_predators_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Behaviour),
+                    (
+                        systems::plant_growth_and_reproduction_system,
+                        systems::herbivore_plant_collision_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Plants),
+                    (
+                        systems::predator_hunting_system,
+                        systems::update_predator_cooldowns,
+                        systems::update_predator_states,
+                        systems::predator_seek_mate_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Predators),
+                    (
+                        systems::reproduction_system
+                            .run_if(not(systems::sexual_reproduction_enabled)),
+                        systems::mating_system.run_if(systems::sexual_reproduction_enabled),
+                        systems::predator_reproduction_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Reproduction),
+                    (
+                        systems::count_births,
+                        systems::count_deaths,
+                        systems::record_lineage_deaths,
+                        systems::update_neat_species,
+                        systems::advance_simulation_time,
+                        systems::exit_after_duration,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Bookkeeping),
+                ),
+            );
+
+        app.add_systems(First, systems::apply_simulation_speed.before(TimeSystem))
+            .add_systems(PreUpdate, systems::process_snapshot_requests)
+            .add_systems(Last, systems::save_snapshot_on_exit)
+            .add_systems(
+                RunFixedMainLoop,
+                systems::run_pending_steps.after(RunFixedMainLoopSystem::FixedMainLoop),
+            );
+
+        if self.spawn_initial_population {
+            app.add_systems(Startup, systems::setup);
+        }
+    }
+}
+
+/// Cámara, sprites de los organismos y límites del mundo ligados a la ventana.
+pub struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, systems::setup_camera)
+            .add_systems(PreUpdate, systems::sync_world_bounds_with_window)
+            .add_systems(
+                Update,
+                (
+                    systems::attach_creature_sprites,
+                    systems::attach_predator_sprites,
+                    systems::attach_plant_sprites,
+                    systems::sync_plant_sprites,
+                ),
+            );
+    }
+}
+
+/// Textos del HUD con estadísticas del ecosistema y FPS.
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+
+        app.add_systems(Startup, systems::setup_hud)
+            .add_systems(Update, (systems::update_hud, systems::update_fps));
+    }
+}
+
+/// Panel con el árbol genealógico de la población (tecla `G`) y selección de organismos.
+pub struct PhylogenyPlugin;
+
+impl Plugin for PhylogenyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedOrganism>()
+            .add_systems(Startup, systems::setup_phylogeny_panel)
+            .add_systems(
+                Update,
+                (
+                    systems::toggle_phylogeny_panel,
+                    systems::phylogeny_node_clicks,
+                    systems::refresh_phylogeny_tree,
+                    systems::update_phylogeny_info,
+                    systems::highlight_selected_organism,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Inspector de decisiones (tecla `I`): por qué el organismo seleccionado se comporta
+/// como lo hace. Los organismos se seleccionan haciendo clic sobre ellos.
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedOrganism>()
+            .add_systems(Startup, systems::setup_inspector_panel)
+            .add_systems(
+                Update,
+                (
+                    systems::toggle_inspector_panel,
+                    systems::select_organism_on_click,
+                    systems::update_inspector,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Capa con los campos de olor del mundo (tecla `O`).
+pub struct PheromoneOverlayPlugin;
+
+impl Plugin for PheromoneOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PheromoneField>()
+            .add_systems(Startup, systems::setup_pheromone_overlay)
+            .add_systems(
+                Update,
+                (
+                    systems::toggle_pheromone_overlay,
+                    systems::update_pheromone_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Controles de teclado: pausa, avance tick a tick, velocidad e instantáneas (F5/F9).
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                systems::speed_keyboard_controls,
+                systems::snapshot_keyboard_controls,
+            ),
+        );
+    }
+}
+
+/// Vigila un archivo de configuración y aplica sus cambios a `SimConfig` sin reiniciar.
+///
+/// Los parámetros de población inicial solo afectan al arranque; el resto se aplica
+/// desde el siguiente tick.
+pub struct ConfigHotReloadPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for ConfigHotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ConfigWatcher::new(self.path.clone()))
+            .add_systems(PreUpdate, systems::hot_reload_config);
+    }
+}
+
+/// Exporta periódicamente las estadísticas del ecosistema a un archivo.
+///
+/// Solo muestrea si existe un [`StatsRecorder`]; se inserta aparte para poder informar
+/// de un archivo inaccesible antes de arrancar.
+pub struct StatsExportPlugin;
+
+impl Plugin for StatsExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            systems::record_stats_sample
+                .run_if(resource_exists::<StatsRecorder>)
+                .in_set(SimulationSet::Bookkeeping)
+                .after(systems::advance_simulation_time)
+                .before(systems::exit_after_duration),
+        );
+    }
+}
+
+/// Exporta el árbol genealógico completo a un archivo al terminar la aplicación.
+///
+/// Con extensión `.nwk` o `.newick` se escribe en Newick; con cualquier otra, en JSON.
+pub struct LineageExportPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for LineageExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LineageExportSettings {
+            path: self.path.clone(),
+        })
+        .add_systems(Last, systems::export_lineage_on_exit);
+    }
+}
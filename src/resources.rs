@@ -1,10 +1,336 @@
+use crate::lineage::OrganismId;
+use crate::utils::field::ScalarField;
+use crate::utils::spatial::SpatialGrid;
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
 
 /// Estadísticas generales del simulador
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Stats {
+    /// Nacimientos de criaturas herbívoras.
     pub total_reproductions: usize,
+    pub predator_births: usize,
     pub total_deaths: usize,
+    pub creature_deaths: DeathCounts,
+    pub predator_deaths: DeathCounts,
     pub max_generation: u32,
     pub simulation_time: f32,
 }
+
+impl Stats {
+    /// Registra una muerte en el total y en el contador de su especie y causa.
+    pub fn record_death(&mut self, species: Species, cause: DeathCause) {
+        self.total_deaths += 1;
+        match species {
+            Species::Creature => self.creature_deaths.record(cause),
+            Species::Predator => self.predator_deaths.record(cause),
+        }
+    }
+}
+
+/// Especies animales del ecosistema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Species {
+    Creature,
+    Predator,
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Species::Creature => "herbívoro",
+            Species::Predator => "depredador",
+        })
+    }
+}
+
+/// Motivo por el que murió un organismo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// Se quedó sin energía.
+    Starvation,
+    /// Superó la edad máxima.
+    OldAge,
+    /// Lo devoró un depredador.
+    Predation,
+}
+
+impl fmt::Display for DeathCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeathCause::Starvation => "hambre",
+            DeathCause::OldAge => "vejez",
+            DeathCause::Predation => "depredación",
+        })
+    }
+}
+
+/// Muertes acumuladas por causa.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeathCounts {
+    pub starvation: usize,
+    pub old_age: usize,
+    pub predation: usize,
+}
+
+impl DeathCounts {
+    pub fn record(&mut self, cause: DeathCause) {
+        match cause {
+            DeathCause::Starvation => self.starvation += 1,
+            DeathCause::OldAge => self.old_age += 1,
+            DeathCause::Predation => self.predation += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.starvation + self.old_age + self.predation
+    }
+}
+
+/// Límites del mundo simulado, centrados en el origen.
+///
+/// En modo con ventana se sincronizan con el tamaño de la ventana;
+/// en modo headless se mantienen fijos.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldBounds {
+    pub half_width: f32,
+    pub half_height: f32,
+}
+
+impl WorldBounds {
+    pub fn from_size(size: Vec2) -> Self {
+        Self {
+            half_width: size.x / 2.0,
+            half_height: size.y / 2.0,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.half_width * 2.0, self.half_height * 2.0)
+    }
+}
+
+impl Default for WorldBounds {
+    /// Mismo tamaño que la ventana por defecto de Bevy (1280x720).
+    fn default() -> Self {
+        Self::from_size(Vec2::new(1280.0, 720.0))
+    }
+}
+
+/// Duración máxima de la simulación en segundos; al alcanzarla la aplicación termina.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct RunDuration(pub f32);
+
+/// Flujos independientes del generador aleatorio de la simulación.
+///
+/// Cada subsistema consume su propio flujo, de modo que añadir tiradas en uno
+/// no altera la secuencia de los demás.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    /// Población inicial.
+    Setup,
+    /// Crecimiento y dispersión de plantas.
+    Plants,
+    /// Nacimientos y mutaciones.
+    Reproduction,
+    /// Ruido de percepción.
+    Perception,
+}
+
+impl RngStream {
+    pub const ALL: [RngStream; 4] = [
+        RngStream::Setup,
+        RngStream::Plants,
+        RngStream::Reproduction,
+        RngStream::Perception,
+    ];
+}
+
+/// Generador aleatorio global y sembrable: una misma semilla reproduce la misma historia.
+///
+/// Su estado completo se serializa en las instantáneas para continuar la misma secuencia.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct SimRng {
+    seed: u64,
+    streams: [ChaCha8Rng; RngStream::ALL.len()],
+}
+
+impl SimRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RngStream::ALL.map(|stream| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                rng.set_stream(stream as u64);
+                rng
+            }),
+        }
+    }
+
+    /// Semilla con la que se creó el generador.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Generador del flujo indicado.
+    pub fn stream(&mut self, stream: RngStream) -> &mut ChaCha8Rng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+impl Default for SimRng {
+    /// Semilla aleatoria; se registra al iniciar para poder repetir la ejecución.
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+/// Índice espacial de la población, reconstruido en cada tick tras el movimiento.
+///
+/// Los sistemas de comportamiento, alimentación, caza y separación lo usan en lugar
+/// de recorrer todas las entidades.
+#[derive(Resource, Default)]
+pub struct SpatialIndex {
+    pub plants: SpatialGrid,
+    pub creatures: SpatialGrid,
+    pub predators: SpatialGrid,
+}
+
+/// Campos de olor del mundo, activos si la configuración tiene `pheromones`.
+///
+/// Las plantas desprenden olor a comida, los depredadores dejan olor a peligro y los
+/// herbívoros un rastro; los tres se difunden y se desvanecen en cada tick.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PheromoneField {
+    pub food: ScalarField,
+    pub danger: ScalarField,
+    pub trail: ScalarField,
+}
+
+impl PheromoneField {
+    /// Ajusta las rejillas a los límites del mundo; si cambian, los campos empiezan
+    /// de cero. Devuelve `true` en ese caso.
+    pub fn fit(&mut self, bounds: &WorldBounds, cell_size: f32) -> bool {
+        let max = Vec2::new(bounds.half_width, bounds.half_height);
+        if self.food.covers(-max, max, cell_size) {
+            return false;
+        }
+        let empty = ScalarField::covering(-max, max, cell_size);
+        self.food = empty.clone();
+        self.danger = empty.clone();
+        self.trail = empty;
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.food.is_empty()
+    }
+
+    pub fn channels_mut(&mut self) -> [&mut ScalarField; 3] {
+        [&mut self.food, &mut self.danger, &mut self.trail]
+    }
+}
+
+/// Control de la velocidad de simulación: pausa, avance tick a tick y multiplicador.
+///
+/// Se aplica sobre `Time<Virtual>`, así que el paso fijo y `Stats::simulation_time`
+/// avanzan en tiempo simulado, no en tiempo real.
+#[derive(Resource, Clone, Debug)]
+pub struct SimulationSpeed {
+    paused: bool,
+    multiplier: f32,
+    pending_steps: u32,
+}
+
+impl Default for SimulationSpeed {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            multiplier: 1.0,
+            pending_steps: 0,
+        }
+    }
+}
+
+impl SimulationSpeed {
+    pub const MIN_MULTIPLIER: f32 = 0.25;
+    pub const MAX_MULTIPLIER: f32 = 64.0;
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        self.multiplier
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Pausa (si no lo estaba) y avanza exactamente un tick.
+    pub fn step(&mut self) {
+        self.paused = true;
+        self.pending_steps += 1;
+    }
+
+    /// Ticks pedidos con [`step`](Self::step) y aún no ejecutados; los consume.
+    pub fn take_pending_steps(&mut self) -> u32 {
+        std::mem::take(&mut self.pending_steps)
+    }
+
+    /// Fija el multiplicador, limitado a `[MIN_MULTIPLIER, MAX_MULTIPLIER]`.
+    pub fn set_multiplier(&mut self, multiplier: f32) {
+        self.multiplier = multiplier.clamp(Self::MIN_MULTIPLIER, Self::MAX_MULTIPLIER);
+    }
+
+    /// Duplica la velocidad.
+    pub fn faster(&mut self) {
+        self.set_multiplier(self.multiplier * 2.0);
+    }
+
+    /// Reduce la velocidad a la mitad.
+    pub fn slower(&mut self) {
+        self.set_multiplier(self.multiplier / 2.0);
+    }
+}
+
+/// Ruta de las instantáneas del mundo y si se guarda una al salir.
+#[derive(Resource, Clone, Debug)]
+pub struct SnapshotSettings {
+    pub path: PathBuf,
+    pub save_on_exit: bool,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("snapshot.ron"),
+            save_on_exit: false,
+        }
+    }
+}
+
+/// Archivo donde exportar el árbol genealógico al salir (`.nwk` para Newick, otro para JSON).
+#[derive(Resource, Clone, Debug)]
+pub struct LineageExportSettings {
+    pub path: PathBuf,
+}
+
+/// Organismo seleccionado en la interfaz (por ejemplo, desde el panel genealógico).
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectedOrganism(pub Option<OrganismId>);
@@ -0,0 +1,341 @@
+use crate::brain::Brain;
+use crate::components::{Creature, Genes, Organism, Perception, Plant, Predator, State, Velocity};
+use crate::config::SimConfig;
+use crate::lineage::LineageStore;
+use crate::neat::NeatState;
+use crate::resources::{PheromoneField, SimRng, Stats, WorldBounds};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fmt, fs, path::Path, path::PathBuf};
+
+/// Versión del formato de instantánea; se incrementa con cada cambio incompatible.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Estado completo del ecosistema: basta para continuar la ejecución exactamente.
+///
+/// Las entidades se guardan en el orden en que las recorren las consultas, de modo
+/// que al restaurarlas el orden de iteración (y con él el consumo del RNG) se conserva.
+#[derive(Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub version: u32,
+    pub config: SimConfig,
+    pub stats: Stats,
+    pub rng: SimRng,
+    /// Límites del mundo: de ellos dependen los rebotes y la rejilla de `pheromones`.
+    pub bounds: WorldBounds,
+    pub lineage: LineageStore,
+    /// Innovaciones y especies NEAT, para que las crías sigan numerándose igual.
+    pub neat: NeatState,
+    /// Campos de olor; vacíos si la configuración no tiene `pheromones`.
+    pub pheromones: PheromoneField,
+    pub plants: Vec<PlantSnapshot>,
+    pub creatures: Vec<CreatureSnapshot>,
+    pub predators: Vec<PredatorSnapshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlantSnapshot {
+    pub position: [f32; 3],
+    pub plant: Plant,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreatureSnapshot {
+    pub position: [f32; 3],
+    pub velocity: [f32; 2],
+    pub organism: Organism,
+    pub creature: Creature,
+    pub genes: GenesSnapshot,
+    pub state: State,
+    /// Hacia dónde mira, que con un cono de visión decide lo que verá.
+    pub heading: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PredatorSnapshot {
+    pub position: [f32; 3],
+    pub velocity: [f32; 2],
+    pub organism: Organism,
+    pub predator: Predator,
+    pub genes: GenesSnapshot,
+    pub state: State,
+    pub heading: [f32; 2],
+}
+
+/// Genes con el color en HSLA, el espacio en el que se generan y mutan.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenesSnapshot {
+    pub traits: BTreeMap<String, f32>,
+    pub color: [f32; 4],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brain: Option<Brain>,
+}
+
+impl From<&Genes> for GenesSnapshot {
+    fn from(genes: &Genes) -> Self {
+        let hsla = Hsla::from(genes.color);
+        Self {
+            traits: genes.traits.clone(),
+            color: [hsla.hue, hsla.saturation, hsla.lightness, hsla.alpha],
+            brain: genes.brain.clone(),
+        }
+    }
+}
+
+impl From<&GenesSnapshot> for Genes {
+    fn from(genes: &GenesSnapshot) -> Self {
+        let [hue, saturation, lightness, alpha] = genes.color;
+        Self {
+            traits: genes.traits.clone(),
+            color: Color::hsla(hue, saturation, lightness, alpha),
+            brain: genes.brain.clone(),
+        }
+    }
+}
+
+/// Error al guardar o cargar una instantánea.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Serialize(ron::Error),
+    Parse {
+        path: PathBuf,
+        source: ron::error::SpannedError,
+    },
+    Version {
+        path: PathBuf,
+        found: u32,
+    },
+    InvalidConfig {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io { path, source } => {
+                write!(f, "no se pudo acceder a '{}': {source}", path.display())
+            }
+            SnapshotError::Serialize(source) => {
+                write!(f, "no se pudo serializar la instantánea: {source}")
+            }
+            SnapshotError::Parse { path, source } => write!(
+                f,
+                "instantánea corrupta en '{}' (línea {}, columna {}): {}",
+                path.display(),
+                source.position.line,
+                source.position.col,
+                source.code
+            ),
+            SnapshotError::Version { path, found } => write!(
+                f,
+                "'{}' usa la versión {found} del formato; se esperaba la {SNAPSHOT_VERSION}",
+                path.display()
+            ),
+            SnapshotError::InvalidConfig { path, problems } => write!(
+                f,
+                "la configuración guardada en '{}' es inválida: {}",
+                path.display(),
+                problems.join("; ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl WorldSnapshot {
+    /// Toma una instantánea del mundo actual.
+    pub fn capture(world: &mut World) -> Self {
+        let plants = world
+            .query::<(&Transform, &Plant)>()
+            .iter(world)
+            .map(|(transform, plant)| PlantSnapshot {
+                position: transform.translation.to_array(),
+                plant: plant.clone(),
+            })
+            .collect();
+
+        let creatures = world
+            .query::<(
+                &Transform,
+                &Velocity,
+                &Organism,
+                &Creature,
+                &Genes,
+                &State,
+                &Perception,
+            )>()
+            .iter(world)
+            .map(
+                |(transform, velocity, organism, creature, genes, state, perception)| {
+                    CreatureSnapshot {
+                        position: transform.translation.to_array(),
+                        velocity: velocity.0.to_array(),
+                        organism: organism.clone(),
+                        creature: creature.clone(),
+                        genes: genes.into(),
+                        state: *state,
+                        heading: perception.heading.to_array(),
+                    }
+                },
+            )
+            .collect();
+
+        let predators = world
+            .query::<(
+                &Transform,
+                &Velocity,
+                &Organism,
+                &Predator,
+                &Genes,
+                &State,
+                &Perception,
+            )>()
+            .iter(world)
+            .map(
+                |(transform, velocity, organism, predator, genes, state, perception)| {
+                    PredatorSnapshot {
+                        position: transform.translation.to_array(),
+                        velocity: velocity.0.to_array(),
+                        organism: organism.clone(),
+                        predator: predator.clone(),
+                        genes: genes.into(),
+                        state: *state,
+                        heading: perception.heading.to_array(),
+                    }
+                },
+            )
+            .collect();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            config: world.resource::<SimConfig>().clone(),
+            stats: world.resource::<Stats>().clone(),
+            rng: world.resource::<SimRng>().clone(),
+            bounds: *world.resource::<WorldBounds>(),
+            lineage: world.resource::<LineageStore>().clone(),
+            neat: world.resource::<NeatState>().clone(),
+            pheromones: world.resource::<PheromoneField>().clone(),
+            plants,
+            creatures,
+            predators,
+        }
+    }
+
+    /// Sustituye el ecosistema actual por el de la instantánea.
+    pub fn restore(self, world: &mut World) {
+        let existing: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(With<Organism>, With<Plant>)>>()
+            .iter(world)
+            .collect();
+        for entity in existing {
+            world.despawn(entity);
+        }
+
+        world.insert_resource(self.config);
+        world.insert_resource(self.stats);
+        world.insert_resource(self.rng);
+        world.insert_resource(self.bounds);
+        world.insert_resource(self.lineage);
+        world.insert_resource(self.neat);
+        world.insert_resource(self.pheromones);
+
+        for creature in self.creatures {
+            world.spawn((
+                Transform::from_translation(Vec3::from_array(creature.position)),
+                GlobalTransform::default(),
+                Visibility::Visible,
+                Velocity(Vec2::from_array(creature.velocity)),
+                creature.organism,
+                creature.creature,
+                Genes::from(&creature.genes),
+                creature.state,
+                Perception {
+                    heading: Vec2::from_array(creature.heading),
+                    ..default()
+                },
+            ));
+        }
+
+        for predator in self.predators {
+            world.spawn((
+                Transform::from_translation(Vec3::from_array(predator.position)),
+                GlobalTransform::default(),
+                Visibility::Visible,
+                Velocity(Vec2::from_array(predator.velocity)),
+                predator.organism,
+                predator.predator,
+                Genes::from(&predator.genes),
+                predator.state,
+                Perception {
+                    heading: Vec2::from_array(predator.heading),
+                    ..default()
+                },
+            ));
+        }
+
+        for plant in self.plants {
+            world.spawn((
+                Transform::from_translation(Vec3::from_array(plant.position)),
+                GlobalTransform::default(),
+                Visibility::Visible,
+                plant.plant,
+            ));
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let path = path.as_ref();
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(SnapshotError::Serialize)?;
+        fs::write(path, text).map_err(|source| SnapshotError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| SnapshotError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        // Se comprueba la versión antes de interpretar el resto del archivo.
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+        let header: Header = ron::from_str(&text).map_err(|source| SnapshotError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::Version {
+                path: path.to_path_buf(),
+                found: header.version,
+            });
+        }
+
+        let snapshot: WorldSnapshot =
+            ron::from_str(&text).map_err(|source| SnapshotError::Parse {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        snapshot
+            .config
+            .validate()
+            .map_err(|problems| SnapshotError::InvalidConfig {
+                path: path.to_path_buf(),
+                problems,
+            })?;
+        Ok(snapshot)
+    }
+}
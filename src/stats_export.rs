@@ -0,0 +1,204 @@
+use crate::resources::Stats;
+use bevy::prelude::*;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Intervalo de muestreo por defecto, en segundos de simulación.
+pub const DEFAULT_STATS_INTERVAL: f32 = 1.0;
+
+/// Fila de la serie temporal del ecosistema.
+///
+/// Los nacimientos y muertes son acumulados desde el inicio de la simulación; las
+/// tasas por intervalo se obtienen restando filas consecutivas.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct StatsSample {
+    pub time: f32,
+    pub creatures: usize,
+    pub predators: usize,
+    pub plants: usize,
+    pub creature_births: usize,
+    pub predator_births: usize,
+    pub creature_deaths_starvation: usize,
+    pub creature_deaths_old_age: usize,
+    pub creature_deaths_predation: usize,
+    pub predator_deaths_starvation: usize,
+    pub predator_deaths_old_age: usize,
+    pub predator_deaths_predation: usize,
+    pub speed_mean: f32,
+    pub speed_variance: f32,
+    pub size_mean: f32,
+    pub size_variance: f32,
+    pub predator_speed_mean: f32,
+    pub predator_speed_variance: f32,
+    pub predator_size_mean: f32,
+    pub predator_size_variance: f32,
+    pub mean_generation: f32,
+    pub max_generation: u32,
+    /// Suma de los tamaños de todas las plantas.
+    pub plant_biomass: f32,
+    /// Especies NEAT con miembros vivos (0 sin cerebros NEAT).
+    pub neat_species: usize,
+    /// Neuronas ocultas medias de los cerebros NEAT.
+    pub neat_hidden_mean: f32,
+    /// Conexiones activas medias de los cerebros NEAT.
+    pub neat_connections_mean: f32,
+}
+
+impl StatsSample {
+    /// Columnas en el orden en que se escriben en CSV.
+    pub const CSV_HEADER: &'static str = "time,creatures,predators,plants,\
+creature_births,predator_births,\
+creature_deaths_starvation,creature_deaths_old_age,creature_deaths_predation,\
+predator_deaths_starvation,predator_deaths_old_age,predator_deaths_predation,\
+speed_mean,speed_variance,size_mean,size_variance,\
+predator_speed_mean,predator_speed_variance,predator_size_mean,predator_size_variance,\
+mean_generation,max_generation,plant_biomass,\
+neat_species,neat_hidden_mean,neat_connections_mean";
+
+    /// Rellena los contadores acumulados a partir de [`Stats`].
+    pub fn with_stats(mut self, stats: &Stats) -> Self {
+        let (creature, predator) = (&stats.creature_deaths, &stats.predator_deaths);
+        self.creature_deaths_starvation = creature.starvation;
+        self.creature_deaths_old_age = creature.old_age;
+        self.creature_deaths_predation = creature.predation;
+        self.predator_deaths_starvation = predator.starvation;
+        self.predator_deaths_old_age = predator.old_age;
+        self.predator_deaths_predation = predator.predation;
+        self.time = stats.simulation_time;
+        self.creature_births = stats.total_reproductions;
+        self.predator_births = stats.predator_births;
+        self.max_generation = stats.max_generation;
+        self
+    }
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{:.3},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.time,
+            self.creatures,
+            self.predators,
+            self.plants,
+            self.creature_births,
+            self.predator_births,
+            self.creature_deaths_starvation,
+            self.creature_deaths_old_age,
+            self.creature_deaths_predation,
+            self.predator_deaths_starvation,
+            self.predator_deaths_old_age,
+            self.predator_deaths_predation,
+            self.speed_mean,
+            self.speed_variance,
+            self.size_mean,
+            self.size_variance,
+            self.predator_speed_mean,
+            self.predator_speed_variance,
+            self.predator_size_mean,
+            self.predator_size_variance,
+            self.mean_generation,
+            self.max_generation,
+            self.plant_biomass,
+            self.neat_species,
+            self.neat_hidden_mean,
+            self.neat_connections_mean,
+        )
+    }
+}
+
+/// Media y varianza poblacional; `(0, 0)` si no hay valores.
+pub fn mean_and_variance(values: impl IntoIterator<Item = f32>) -> (f32, f32) {
+    // Algoritmo de Welford: estable aunque haya miles de valores parecidos.
+    let (mut count, mut mean, mut m2) = (0u32, 0.0f64, 0.0f64);
+    for value in values {
+        count += 1;
+        let delta = value as f64 - mean;
+        mean += delta / count as f64;
+        m2 += delta * (value as f64 - mean);
+    }
+    if count == 0 {
+        (0.0, 0.0)
+    } else {
+        (mean as f32, (m2 / count as f64) as f32)
+    }
+}
+
+/// Formato del archivo de series temporales.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    Csv,
+    JsonLines,
+}
+
+impl StatsFormat {
+    /// Deduce el formato de la extensión: `.jsonl`/`.ndjson`/`.json` son JSON Lines; el resto, CSV.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("jsonl" | "ndjson" | "json") => StatsFormat::JsonLines,
+            _ => StatsFormat::Csv,
+        }
+    }
+}
+
+/// Escribe una muestra de estadísticas cada `interval` segundos de simulación.
+#[derive(Resource)]
+pub struct StatsRecorder {
+    format: StatsFormat,
+    interval: f32,
+    next_sample: f32,
+    /// `None` tras un error de escritura: se deja de exportar sin detener la simulación.
+    writer: Option<BufWriter<File>>,
+}
+
+impl StatsRecorder {
+    /// Crea (o trunca) el archivo de salida y escribe la cabecera si es CSV.
+    pub fn create(path: impl AsRef<Path>, interval: f32) -> io::Result<Self> {
+        let path = path.as_ref();
+        let format = StatsFormat::from_path(path);
+        let mut writer = BufWriter::new(File::create(path)?);
+        if format == StatsFormat::Csv {
+            writeln!(writer, "{}", StatsSample::CSV_HEADER)?;
+            writer.flush()?;
+        }
+        Ok(Self {
+            format,
+            interval: interval.max(f32::EPSILON),
+            next_sample: 0.0,
+            writer: Some(writer),
+        })
+    }
+
+    pub fn format(&self) -> StatsFormat {
+        self.format
+    }
+
+    /// Indica si toca tomar una muestra en el instante `time` y, si es así, programa la siguiente.
+    pub fn due(&mut self, time: f32) -> bool {
+        if self.writer.is_none() || time < self.next_sample {
+            return false;
+        }
+        // Siguiente múltiplo del intervalo; al continuar una instantánea el tiempo
+        // puede ir muy por delante de la última muestra.
+        self.next_sample = ((time / self.interval).floor() + 1.0) * self.interval;
+        true
+    }
+
+    /// Añade una fila al archivo y la vuelca a disco para poder seguirlo en vivo.
+    pub fn write(&mut self, sample: &StatsSample) -> io::Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        let result = match self.format {
+            StatsFormat::Csv => writeln!(writer, "{}", sample.csv_row()),
+            StatsFormat::JsonLines => serde_json::to_writer(&mut *writer, sample)
+                .map_err(io::Error::other)
+                .and_then(|()| writeln!(writer)),
+        }
+        .and_then(|()| writer.flush());
+
+        if result.is_err() {
+            self.writer = None;
+        }
+        result
+    }
+}
@@ -1,14 +1,13 @@
 use crate::components::Velocity;
+use crate::resources::WorldBounds;
 use bevy::prelude::*;
 
 pub fn boundary_bounce_system(
     mut query: Query<(&mut Transform, &mut Velocity)>,
-    windows: Query<&Window>,
+    bounds: Res<WorldBounds>,
 ) {
-    let window = windows.single().expect("Window not found");
-
-    let half_width = window.width() / 2.0;
-    let half_height = window.height() / 2.0;
+    let half_width = bounds.half_width;
+    let half_height = bounds.half_height;
 
     for (mut transform, mut velocity) in query.iter_mut() {
         let pos = &mut transform.translation;
@@ -22,3 +21,15 @@ pub fn boundary_bounce_system(
         }
     }
 }
+
+/// Ajusta los límites del mundo al tamaño actual de la ventana (solo en modo con ventana).
+pub fn sync_world_bounds_with_window(mut bounds: ResMut<WorldBounds>, windows: Query<&Window>) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let size = Vec2::new(window.width(), window.height());
+    if bounds.size() != size {
+        *bounds = WorldBounds::from_size(size);
+    }
+}
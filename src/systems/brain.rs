@@ -0,0 +1,111 @@
+use crate::brain::{BrainOutput, Sensors};
+use crate::components::{Creature, Genes, Organism, Percept, Perception, State, Velocity};
+use crate::config::SimConfig;
+use crate::events::StateChangedEvent;
+use crate::neat::NeatState;
+use crate::resources::Species;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+/// Los herbívoros con cerebro deciden con su red neuronal hacia dónde moverse y si
+/// quieren comer o reproducirse.
+///
+/// La decisión se expresa con el mismo `State` que usa la máquina de estados:
+/// `SeekingFood` para comer y `Reproducing` para reproducirse. Los sistemas de
+/// alimentación y reproducción solo dejan actuar a un herbívoro con cerebro cuando
+/// está en el estado correspondiente.
+///
+/// Manda el genoma de cada herbívoro y no la configuración: quien nació con cerebro lo
+/// conserva aunque una recarga quite `genome.brain`, porque la máquina de estados ya
+/// no lo controla.
+#[allow(clippy::type_complexity)]
+pub fn brain_system(
+    config: Res<SimConfig>,
+    mut changes: EventWriter<StateChangedEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut State,
+            &Organism,
+            &Genes,
+            &Perception,
+        ),
+        With<Creature>,
+    >,
+) {
+    for (entity, transform, mut velocity, mut state, organism, genes, perception) in
+        query.iter_mut()
+    {
+        let Some(brain) = &genes.brain else {
+            continue;
+        };
+        let sensors = read_sensors(&config, transform, organism, genes, perception);
+        let decision = brain.decide(&sensors);
+        velocity.0 = decision.steer * genes.speed();
+
+        let new_state = decided_state(&decision);
+        if *state != new_state {
+            changes.write(StateChangedEvent {
+                entity,
+                species: Species::Creature,
+                from: *state,
+                to: new_state,
+            });
+            *state = new_state;
+        }
+    }
+}
+
+/// Estado con el que se expresa una decisión: reproducirse tiene prioridad sobre comer.
+pub fn decided_state(decision: &BrainOutput) -> State {
+    if decision.reproduce {
+        State::Reproducing
+    } else if decision.eat {
+        State::SeekingFood
+    } else {
+        State::Wandering
+    }
+}
+
+/// Sensores de un herbívoro con cerebro a partir de lo que ve.
+pub fn read_sensors(
+    config: &SimConfig,
+    transform: &Transform,
+    organism: &Organism,
+    genes: &Genes,
+    perception: &Perception,
+) -> Sensors {
+    let creatures = &config.creatures;
+    let pos = transform.translation.truncate();
+    let sense = |percept: Option<Percept>| match percept {
+        Some(percept) => (
+            (percept.position - pos).normalize_or_zero(),
+            (1.0 - percept.distance / perception.range).max(0.0),
+        ),
+        None => (Vec2::ZERO, 0.0),
+    };
+    let (plant_direction, plant_proximity) = sense(perception.plant);
+    let (predator_direction, predator_proximity) = sense(perception.predator);
+    Sensors {
+        plant_direction,
+        plant_proximity,
+        predator_direction,
+        predator_proximity,
+        energy: organism.energy
+            / genes.get_or(Genes::REPRODUCTION_ENERGY, creatures.reproduction_energy),
+        age: organism.age / genes.get_or(Genes::LIFESPAN, creatures.max_age),
+    }
+}
+
+/// Recuenta los miembros vivos de cada especie NEAT y da por extinguidas las vacías.
+pub fn update_neat_species(mut neat: ResMut<NeatState>, query: Query<&Genes, With<Creature>>) {
+    let mut members: BTreeMap<u32, usize> = BTreeMap::new();
+    for genes in &query {
+        if let Some(species) = genes.brain.as_ref().and_then(|brain| brain.neat()?.species) {
+            *members.entry(species).or_default() += 1;
+        }
+    }
+    neat.species.recount(&members);
+}
@@ -0,0 +1,7 @@
+use crate::resources::Stats;
+use bevy::prelude::*;
+
+/// Acumula el tiempo de simulación transcurrido.
+pub fn advance_simulation_time(time: Res<Time>, mut stats: ResMut<Stats>) {
+    stats.simulation_time += time.delta_secs();
+}
@@ -1,29 +1,33 @@
-use bevy::prelude::*;
 use crate::components::Velocity;
-
-const MIN_SEPARATION_DISTANCE: f32 = 15.0;
-const SEPARATION_FORCE: f32 = 20.0;
+use crate::config::SimConfig;
+use crate::resources::SpatialIndex;
+use bevy::prelude::*;
 
 pub fn avoid_entity_overlap_system(
-    mut query: Query<(&Transform, &mut Velocity)>,
+    config: Res<SimConfig>,
+    index: Res<SpatialIndex>,
+    mut query: Query<(Entity, &Transform, &mut Velocity)>,
 ) {
-    let positions: Vec<Vec2> = query
-        .iter()
-        .map(|(t, _)| t.translation.truncate())
-        .collect();
+    let min_distance = config.collisions.min_separation_distance;
+    let force = config.collisions.separation_force;
+
+    for (entity, transform, mut velocity) in query.iter_mut() {
+        let pos = transform.translation.truncate();
 
-    for (i, (transform_i, mut velocity_i)) in query.iter_mut().enumerate() {
-        let pos_i = transform_i.translation.truncate();
+        let neighbours = index
+            .creatures
+            .within(pos, min_distance)
+            .chain(index.predators.within(pos, min_distance));
 
-        for (j, pos_j) in positions.iter().enumerate() {
-            if i == j {
+        for (other, other_pos) in neighbours {
+            if other == entity {
                 continue;
             }
 
-            let distance = pos_i.distance(*pos_j);
-            if distance < MIN_SEPARATION_DISTANCE && distance > 0.01 {
-                let push = (pos_i - *pos_j).normalize_or_zero() * SEPARATION_FORCE / distance;
-                velocity_i.0 += push;
+            let distance = pos.distance(other_pos);
+            if distance > 0.01 {
+                let push = (pos - other_pos).normalize_or_zero() * force / distance;
+                velocity.0 += push;
             }
         }
     }
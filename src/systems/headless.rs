@@ -0,0 +1,51 @@
+use crate::components::{Creature, Plant, Predator};
+use crate::resources::{RunDuration, Stats};
+use bevy::prelude::*;
+
+/// Cada cuántos segundos de simulación se registra el progreso en modo headless.
+const PROGRESS_LOG_INTERVAL: f32 = 10.0;
+
+/// Registra periódicamente el estado de la población (útil sin HUD).
+pub fn log_headless_progress(
+    stats: Res<Stats>,
+    mut last_logged: Local<f32>,
+    creatures: Query<(), With<Creature>>,
+    predators: Query<(), With<Predator>>,
+    plants: Query<(), With<Plant>>,
+) {
+    if stats.simulation_time - *last_logged < PROGRESS_LOG_INTERVAL {
+        return;
+    }
+    *last_logged = stats.simulation_time;
+
+    info!(
+        "⏱️ {:.0}s | 🧬 {} | 🦊 {} | 🌿 {} | 🔁 {} | 💀 {} (🍂 {} ⌛ {} 🦷 {}) | 📈 Gen {}",
+        stats.simulation_time,
+        creatures.iter().count(),
+        predators.iter().count(),
+        plants.iter().count(),
+        stats.total_reproductions,
+        stats.total_deaths,
+        stats.creature_deaths.starvation + stats.predator_deaths.starvation,
+        stats.creature_deaths.old_age + stats.predator_deaths.old_age,
+        stats.creature_deaths.predation,
+        stats.max_generation,
+    );
+}
+
+/// Termina la aplicación cuando se alcanza la duración de simulación pedida.
+pub fn exit_after_duration(
+    stats: Res<Stats>,
+    duration: Option<Res<RunDuration>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Some(duration) = duration
+        && stats.simulation_time >= duration.0
+    {
+        info!(
+            "🏁 Simulación completada tras {:.1}s",
+            stats.simulation_time
+        );
+        exit.write(AppExit::Success);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::config::{ConfigWatcher, SimConfig};
+use bevy::prelude::*;
+
+/// Recarga `SimConfig` cuando cambia el archivo vigilado y registra qué valores cambiaron.
+///
+/// Si el archivo nuevo no es válido se mantiene la configuración actual.
+pub fn hot_reload_config(
+    time: Res<Time<Real>>,
+    mut watcher: ResMut<ConfigWatcher>,
+    mut config: ResMut<SimConfig>,
+) {
+    if !watcher.poll_changed(time.delta()) {
+        return;
+    }
+
+    let new_config = match SimConfig::load(&watcher.path) {
+        Ok(new_config) => new_config,
+        Err(error) => {
+            error!("❌ Recarga de configuración descartada: {error}");
+            return;
+        }
+    };
+
+    let changes = config.diff(&new_config);
+    if changes.is_empty() {
+        info!("🔧 '{}' guardado sin cambios", watcher.path.display());
+        return;
+    }
+
+    info!(
+        "🔧 Configuración recargada desde '{}' ({} cambios):",
+        watcher.path.display(),
+        changes.len()
+    );
+    for change in &changes {
+        info!("   {change}");
+    }
+    *config = new_config;
+}
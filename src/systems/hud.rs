@@ -1,22 +1,58 @@
 use crate::{
-    components::{Creature, FpsText, Organism, Predator, Plant},
-    resources::Stats,
+    components::{Creature, FpsText, HudText, Organism, Plant, Predator},
+    resources::{SimulationSpeed, Stats},
 };
 use bevy::prelude::*;
 
+/// Crea los textos del HUD (FPS y estadísticas generales).
+pub fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // FPS Text
+    commands
+        .spawn((
+            Text::new("FPS: "),
+            TextFont {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 30.0,
+                ..default()
+            },
+        ))
+        .with_child((
+            TextSpan::from(""),
+            TextFont {
+                font_size: 24.0,
+                ..default()
+            },
+            FpsText,
+        ));
+
+    // HUD general
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        HudText,
+    ));
+}
+
 /// Actualiza el texto del HUD con estadísticas vivas del ecosistema.
 pub fn update_hud(
-    mut stats: ResMut<Stats>,
-    time: Res<Time>,
+    stats: Res<Stats>,
+    speed: Res<SimulationSpeed>,
     creatures: Query<(&Organism, &Creature)>,
     predators: Query<&Predator>,
     plants: Query<(), With<Plant>>,
-    mut texts: Query<&mut Text>,
+    mut texts: Query<&mut Text, With<HudText>>,
 ) {
-    // Actualizar tiempo de simulación
-    stats.simulation_time += time.delta_secs();
-
-    if let Some(mut text) = texts.iter_mut().last() {
+    if let Ok(mut text) = texts.single_mut() {
         let total_creatures = creatures.iter().count();
         let total_predators = predators.iter().count();
         let plant_count = plants.iter().count();
@@ -28,23 +64,38 @@ pub fn update_hud(
             0.0
         };
 
+        let speed_line = if speed.is_paused() {
+            "⏸️ Pausado".to_string()
+        } else {
+            format!("▶️ Velocidad: {}x", speed.multiplier())
+        };
+
         *text = Text::new(format!(
             "🧬 Criaturas: {}\n\
              🦊 Depredadores: {}\n\
              🌿 Plantas: {}\n\
              🔁 Reproducciones: {}\n\
              💀 Muertes: {}\n\
+             🐑 Herbívoros: 🍂 {} hambre | ⌛ {} vejez | 🦷 {} cazados\n\
+             🐺 Depredadores: 🍂 {} hambre | ⌛ {} vejez\n\
              📈 Máx Gen: {}\n\
              📊 Prom Gen: {:.1}\n\
-             ⏱️ Tiempo: {:.1}s",
+             ⏱️ Tiempo: {:.1}s\n\
+             {}",
             total_creatures,
             total_predators,
             plant_count,
             stats.total_reproductions,
             stats.total_deaths,
+            stats.creature_deaths.starvation,
+            stats.creature_deaths.old_age,
+            stats.creature_deaths.predation,
+            stats.predator_deaths.starvation,
+            stats.predator_deaths.old_age,
             stats.max_generation,
             avg_gen,
-            stats.simulation_time
+            stats.simulation_time,
+            speed_line
         ));
     }
 }
@@ -55,10 +106,11 @@ pub fn update_fps(
     mut query: Query<&mut TextSpan, With<FpsText>>,
 ) {
     for mut span in &mut query {
-        if let Some(fps) = diagnostics.get(&bevy::diagnostic::FrameTimeDiagnosticsPlugin::FPS) {
-            if let Some(value) = fps.smoothed() {
-                **span = format!("{value:.2}");
-            }
+        if let Some(value) = diagnostics
+            .get(&bevy::diagnostic::FrameTimeDiagnosticsPlugin::FPS)
+            .and_then(|fps| fps.smoothed())
+        {
+            **span = format!("{value:.2}");
         }
     }
 }
@@ -0,0 +1,497 @@
+use crate::brain::{Brain, INPUT_NAMES, OUTPUT_NAMES, interpret};
+use crate::components::{Creature, Genes, Organism, Perception, Predator, State, Velocity};
+use crate::config::SimConfig;
+use crate::neat::{NeatGenome, NodeId, NodeKind};
+use crate::resources::{PheromoneField, SelectedOrganism, SpatialIndex};
+use crate::systems::brain::{decided_state, read_sensors};
+use crate::systems::mating::compatible;
+use crate::systems::pheromones::{danger_scent, food_scent};
+use crate::systems::phylogeny::selection_radius;
+use crate::systems::states::choose_state;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::fmt::Write;
+
+const PANEL_WIDTH: f32 = 440.0;
+/// Conexiones NEAT listadas, de mayor a menor peso absoluto.
+const STRONGEST_CONNECTIONS: usize = 5;
+
+/// Raíz del inspector de decisiones.
+#[derive(Component)]
+pub struct InspectorPanel;
+
+/// Texto con la decisión del organismo seleccionado.
+#[derive(Component)]
+pub struct InspectorText;
+
+/// Crea el inspector de decisiones, oculto hasta pulsar `I`.
+pub fn setup_inspector_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            width: Val::Px(PANEL_WIDTH),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        InspectorPanel,
+        children![(
+            Text::new("Haz clic en un organismo para inspeccionarlo"),
+            TextFont {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 14.0,
+                ..default()
+            },
+            InspectorText,
+        )],
+    ));
+}
+
+/// Muestra u oculta el inspector con la tecla `I`.
+pub fn toggle_inspector_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut panels: Query<&mut Node, With<InspectorPanel>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyI) {
+        return;
+    }
+    for mut node in panels.iter_mut() {
+        node.display = match node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+/// Al hacer clic sobre un organismo del mundo se selecciona.
+///
+/// Los clics sobre la interfaz (por ejemplo, las ramas del panel genealógico) se ignoran.
+pub fn select_organism_on_click(
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    interactions: Query<&Interaction>,
+    organisms: Query<(&Organism, &Transform, Option<&Genes>)>,
+    mut selected: ResMut<SelectedOrganism>,
+) {
+    if !buttons.just_pressed(MouseButton::Left)
+        || interactions
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), cameras.single()) else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let Ok(point) = camera.viewport_to_world_2d(camera_transform, cursor) else {
+        return;
+    };
+
+    let clicked = organisms
+        .iter()
+        .map(|(organism, transform, genes)| {
+            (
+                organism.id,
+                transform.translation.truncate().distance(point),
+                selection_radius(genes),
+            )
+        })
+        .filter(|(_, distance, radius)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    if let Some((id, ..)) = clicked {
+        selected.0 = Some(id);
+    }
+}
+
+/// Describe por qué el organismo seleccionado se comporta como lo hace.
+///
+/// Reutiliza las mismas funciones y la misma [`Perception`] que los sistemas de
+/// comportamiento, así que los umbrales, objetivos y sensores mostrados son los que
+/// se usan en el tick actual.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_inspector(
+    config: Res<SimConfig>,
+    field: Res<PheromoneField>,
+    index: Res<SpatialIndex>,
+    selected: Res<SelectedOrganism>,
+    panels: Query<&Node, With<InspectorPanel>>,
+    creatures: Query<(
+        Entity,
+        &Organism,
+        &Creature,
+        &Transform,
+        &Velocity,
+        &State,
+        &Genes,
+        &Perception,
+    )>,
+    predators: Query<(&Organism, &Predator, &Velocity, &State, &Genes, &Perception)>,
+    mut texts: Query<&mut Text, With<InspectorText>>,
+) {
+    if panels.iter().all(|node| node.display == Display::None) {
+        return;
+    }
+    let Some(id) = selected.0 else {
+        return;
+    };
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+
+    let description = if let Some(creature) = creatures.iter().find(|(_, o, ..)| o.id == id) {
+        let in_heat: HashMap<Entity, &Genes> = creatures
+            .iter()
+            .filter(|(.., state, _, _)| **state == State::Reproducing)
+            .map(|(entity, .., genes, _)| (entity, genes))
+            .collect();
+        describe_creature(&config, &field, &index, &in_heat, creature)
+    } else if let Some(predator) = predators.iter().find(|(o, ..)| o.id == id) {
+        describe_predator(&config, predator)
+    } else {
+        format!("#{id} ya no está vivo")
+    };
+    *text = Text::new(description);
+}
+
+fn describe_creature(
+    config: &SimConfig,
+    field: &PheromoneField,
+    index: &SpatialIndex,
+    in_heat: &HashMap<Entity, &Genes>,
+    (entity, organism, creature, transform, velocity, state, genes, perception): (
+        Entity,
+        &Organism,
+        &Creature,
+        &Transform,
+        &Velocity,
+        &State,
+        &Genes,
+        &Perception,
+    ),
+) -> String {
+    let creatures = &config.creatures;
+    let hunger = genes.get_or(Genes::HUNGER_THRESHOLD, creatures.hunger_threshold);
+    let reproduction = genes.get_or(Genes::REPRODUCTION_ENERGY, creatures.reproduction_energy);
+    let lifespan = genes.get_or(Genes::LIFESPAN, creatures.max_age);
+
+    let mut out = format!(
+        "🔍 #{} · Herbívoro · Gen {} · {:?}\n",
+        organism.id, organism.generation, state
+    );
+    let _ = writeln!(
+        out,
+        "⚡ Energía {:.1} · hambre < {hunger:.1} · celo > {reproduction:.1}",
+        organism.energy
+    );
+    let _ = writeln!(
+        out,
+        "⏳ Desde la última cría {:.1}s / {:.1}s · edad {:.1}s / {lifespan:.1}s",
+        creature.time_since_reproduction, creatures.reproduction_cooldown, organism.age
+    );
+    out.push_str(&describe_vision(perception));
+
+    // Como en `brain_system`, manda el genoma y no `genome.brain`.
+    match &genes.brain {
+        Some(brain) => {
+            let sensors = read_sensors(config, transform, organism, genes, perception);
+            let inputs = sensors.to_inputs();
+            let outputs = brain.outputs(&sensors);
+            let decision = interpret(outputs);
+            let _ = writeln!(out, "{}", describe_brain(brain));
+            out.push_str("Sensores:\n");
+            for (name, value) in INPUT_NAMES.iter().zip(inputs) {
+                let _ = writeln!(out, "  {name:<20} {value:+.2} {}", bar(value));
+            }
+            out.push_str("Salidas:\n");
+            for (name, value) in OUTPUT_NAMES.iter().zip(outputs) {
+                let _ = writeln!(out, "  {name:<20} {value:+.2} {}", bar(value));
+            }
+            let _ = writeln!(
+                out,
+                "Decisión de la red: {:?} (comer {}, reproducirse {})",
+                decided_state(&decision),
+                yes_no(decision.eat),
+                yes_no(decision.reproduce)
+            );
+            if let Brain::Neat(genome) = brain {
+                out.push_str(&strongest_connections(genome));
+            }
+        }
+        None => {
+            let decided = choose_state(config, creature, organism, genes);
+            let reason = match decided {
+                State::SeekingFood => "energía bajo el umbral de hambre",
+                State::Reproducing => "energía sobre el umbral de celo y espera cumplida",
+                _ if organism.energy > reproduction => "espera sin cumplir",
+                _ => "energía entre los umbrales",
+            };
+            let _ = writeln!(out, "Decisión de update_states: {decided:?} ({reason})");
+
+            let pos = transform.translation.truncate();
+            let seeking = *state == State::SeekingFood;
+            match &perception.plant {
+                Some(plant) => {
+                    let _ = writeln!(
+                        out,
+                        "🌿 seek_food: ve una planta a {:.0} {}",
+                        plant.distance,
+                        if seeking {
+                            "→ va hacia ella"
+                        } else {
+                            "(inactivo: no tiene hambre)"
+                        }
+                    );
+                }
+                None => match food_scent(config, field, pos) {
+                    Some(_) => {
+                        let _ = writeln!(
+                            out,
+                            "🌿 seek_food: no ve ninguna planta, pero la huele (olor {:.2}) {}",
+                            field.food.value(pos),
+                            if seeking {
+                                "→ sigue el olor"
+                            } else {
+                                "(inactivo: no tiene hambre)"
+                            }
+                        );
+                    }
+                    None => out.push_str("🌿 seek_food: no ve ni huele ninguna planta\n"),
+                },
+            }
+
+            let flee_radius = creatures.flee_radius;
+            let fleeing = matches!(state, State::Wandering | State::SeekingFood);
+            match &perception.predator {
+                Some(predator) if predator.distance < flee_radius => {
+                    let _ = writeln!(
+                        out,
+                        "🦊 avoid_predators: depredador a {:.0} (< {flee_radius:.0}) {}",
+                        predator.distance,
+                        if fleeing {
+                            "→ huye"
+                        } else {
+                            "(inactivo: en celo)"
+                        }
+                    );
+                }
+                Some(predator) => {
+                    let _ = writeln!(
+                        out,
+                        "🦊 avoid_predators: ve un depredador a {:.0}, aún lejos (huye a menos de {flee_radius:.0})",
+                        predator.distance
+                    );
+                }
+                None => out.push_str("🦊 avoid_predators: no ve ningún depredador\n"),
+            }
+            let near = perception
+                .predator
+                .is_some_and(|p| p.distance < flee_radius);
+            if !near && danger_scent(config, field, pos).is_some() {
+                let _ = writeln!(
+                    out,
+                    "🦊 avoid_predators: huele peligro ({:.2}) {}",
+                    field.danger.value(pos),
+                    if fleeing {
+                        "→ huye del olor"
+                    } else {
+                        "(inactivo: en celo)"
+                    }
+                );
+            }
+        }
+    }
+
+    if config.mating.sexual {
+        let pos = transform.translation.truncate();
+        out.push_str(&describe_partner(
+            config, index, in_heat, entity, pos, state, genes, perception,
+        ));
+    }
+
+    out.push_str(&describe_velocity(velocity, genes));
+    out
+}
+
+/// Pareja hacia la que va `seek_mate_system`, o por qué no tiene ninguna.
+///
+/// Elige la misma pareja que el sistema, pero sin el ruido de percepción para no
+/// gastar números del generador de la simulación.
+#[allow(clippy::too_many_arguments)]
+fn describe_partner(
+    config: &SimConfig,
+    index: &SpatialIndex,
+    in_heat: &HashMap<Entity, &Genes>,
+    entity: Entity,
+    pos: Vec2,
+    state: &State,
+    genes: &Genes,
+    perception: &Perception,
+) -> String {
+    if *state != State::Reproducing {
+        return "💞 seek_mate: inactivo (no está en celo)\n".to_string();
+    }
+    let mating = &config.mating;
+    let candidate = |other: Entity, other_pos: Vec2| {
+        other != entity
+            && in_heat.contains_key(&other)
+            && pos.distance(other_pos) < mating.search_radius
+            && perception.sees(pos, other_pos)
+    };
+    let partner = index
+        .creatures
+        .nearest_where(pos, perception.range, |other, other_pos| {
+            candidate(other, other_pos) && compatible(config, genes, in_heat[&other])
+        });
+    if let Some((_, partner_pos)) = partner {
+        let distance = pos.distance(partner_pos);
+        let action = if distance < mating.mate_radius {
+            "→ se aparean"
+        } else if genes.brain.is_some() {
+            "(el rumbo lo decide la red)"
+        } else {
+            "→ va hacia ella"
+        };
+        return format!("💞 seek_mate: pareja compatible a {distance:.0} {action}\n");
+    }
+    match index
+        .creatures
+        .nearest_where(pos, perception.range, candidate)
+    {
+        Some((other, other_pos)) => format!(
+            "💞 seek_mate: ve una pareja en celo a {:.0}, pero no es compatible (distancia genética {:.2}, máx {:.2}, o de otra especie)\n",
+            pos.distance(other_pos),
+            genes.distance(in_heat[&other], &config.genome),
+            mating.max_genetic_distance
+        ),
+        None => format!(
+            "💞 seek_mate: no ve ninguna pareja en celo a menos de {:.0}\n",
+            mating.search_radius
+        ),
+    }
+}
+
+fn describe_predator(
+    config: &SimConfig,
+    (organism, predator, velocity, state, genes, perception): (
+        &Organism,
+        &Predator,
+        &Velocity,
+        &State,
+        &Genes,
+        &Perception,
+    ),
+) -> String {
+    let predators = &config.predators;
+    let mut out = format!(
+        "🔍 #{} · Depredador · Gen {} · {:?}\n",
+        organism.id, organism.generation, state
+    );
+    let _ = writeln!(
+        out,
+        "⚡ Energía {:.1} · caza < {:.1} · temporada > {:.1} · máx {:.1}",
+        organism.energy, predators.hunger_threshold, predators.season_energy, predators.max_energy
+    );
+    let _ = writeln!(
+        out,
+        "⏳ Espera de reproducción {:.1}s · forcejeo {:.1}s · edad {:.1}s",
+        predator.reproduction_cooldown.max(0.0),
+        predator.grip,
+        organism.age
+    );
+    out.push_str(&describe_vision(perception));
+    match &perception.prey {
+        Some(prey) => {
+            let _ = writeln!(out, "🎯 Ve una presa a {:.0}", prey.distance);
+        }
+        None => out.push_str("🎯 No ve ninguna presa\n"),
+    }
+    out.push_str(&describe_velocity(velocity, genes));
+    out
+}
+
+fn describe_vision(perception: &Perception) -> String {
+    format!(
+        "👁️ Alcance {:.0} · ángulo {:.0}° · mira hacia ({:.2}, {:.2})\n",
+        perception.range,
+        perception.field_of_view.to_degrees(),
+        perception.heading.x,
+        perception.heading.y
+    )
+}
+
+fn describe_velocity(velocity: &Velocity, genes: &Genes) -> String {
+    let speed = velocity.0.length();
+    let relative = if genes.speed() > 0.0 {
+        speed / genes.speed()
+    } else {
+        0.0
+    };
+    format!(
+        "🏃 Velocidad ({:.1}, {:.1}) · {speed:.1} ({relative:.2}× su velocidad genética {:.1})",
+        velocity.0.x,
+        velocity.0.y,
+        genes.speed()
+    )
+}
+
+fn describe_brain(brain: &Brain) -> String {
+    match brain {
+        Brain::Fixed(network) => format!(
+            "🧠 Red fija · {} neuronas ocultas · {} pesos",
+            network.hidden,
+            network.weights.len()
+        ),
+        Brain::Neat(genome) => format!(
+            "🧠 Red NEAT · especie {} · {} neuronas ocultas · {}/{} conexiones activas",
+            genome
+                .species
+                .map_or("-".to_string(), |species| format!("#{species}")),
+            genome.hidden.len(),
+            genome.enabled_connections(),
+            genome.connections.len()
+        ),
+    }
+}
+
+fn strongest_connections(genome: &NeatGenome) -> String {
+    let mut enabled: Vec<_> = genome.connections.iter().filter(|c| c.enabled).collect();
+    enabled.sort_by(|a, b| b.weight.abs().total_cmp(&a.weight.abs()));
+    let mut out = String::from("Conexiones más fuertes:\n");
+    for c in enabled.iter().take(STRONGEST_CONNECTIONS) {
+        let _ = writeln!(
+            out,
+            "  {} → {} {:+.2}",
+            node_name(c.from),
+            node_name(c.to),
+            c.weight
+        );
+    }
+    out
+}
+
+fn node_name(node: NodeId) -> String {
+    match NeatGenome::node_kind(node) {
+        NodeKind::Input => INPUT_NAMES[node as usize].to_string(),
+        NodeKind::Bias => "sesgo".to_string(),
+        NodeKind::Output => OUTPUT_NAMES[node as usize - INPUT_NAMES.len() - 1].to_string(),
+        NodeKind::Hidden => format!("oculta {node}"),
+    }
+}
+
+/// Barra de hasta cinco bloques proporcional al valor absoluto (recortado a 1).
+fn bar(value: f32) -> String {
+    let blocks = (value.abs().min(1.0) * 5.0).round() as usize;
+    "#".repeat(blocks) + &"·".repeat(5 - blocks)
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "sí" } else { "no" }
+}
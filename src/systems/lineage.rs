@@ -0,0 +1,36 @@
+use crate::events::DeathEvent;
+use crate::lineage::LineageStore;
+use crate::resources::{LineageExportSettings, Species, Stats};
+use bevy::prelude::*;
+
+/// Anota en el árbol genealógico la fecha y la causa de cada muerte.
+pub fn record_lineage_deaths(
+    mut deaths: EventReader<DeathEvent>,
+    stats: Res<Stats>,
+    mut lineage: ResMut<LineageStore>,
+) {
+    for death in deaths.read() {
+        lineage.record_death(death.id, stats.simulation_time, death.cause);
+    }
+}
+
+/// Exporta el árbol genealógico al terminar la aplicación.
+pub fn export_lineage_on_exit(
+    mut exits: EventReader<AppExit>,
+    settings: Res<LineageExportSettings>,
+    lineage: Res<LineageStore>,
+) {
+    if exits.read().next().is_none() {
+        return;
+    }
+
+    match lineage.export(&settings.path) {
+        Ok(()) => info!(
+            "🌳 Árbol genealógico guardado en '{}' ({} organismos, {} linajes de herbívoros supervivientes)",
+            settings.path.display(),
+            lineage.len(),
+            lineage.surviving_lineages(Species::Creature).len()
+        ),
+        Err(error) => error!("❌ {error}"),
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    components::{Creature, Genes, Organism, Perception, Predator, State, Velocity},
+    config::{PredatorConfig, SimConfig},
+    events::{BirthEvent, MutationEvent},
+    lineage::LineageStore,
+    neat::NeatState,
+    resources::{RngStream, SimRng, SpatialIndex, Species, Stats},
+    systems::perception::look_where,
+    utils::factory::{crossover_genes, inherit_genes, spawn_child_creature},
+    utils::spatial::SpatialGrid,
+};
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use rand::prelude::*;
+
+/// Condición de ejecución: la reproducción sexual está activada en la configuración.
+pub fn sexual_reproduction_enabled(config: Res<SimConfig>) -> bool {
+    config.mating.sexual
+}
+
+/// Empareja a los candidatos dos a dos: cada uno, en orden, con el candidato libre
+/// más cercano a menos de `radius` que `accept` dé por bueno.
+pub fn pair_nearby(
+    ready: &[(Entity, Vec2)],
+    grid: &SpatialGrid,
+    radius: f32,
+    mut accept: impl FnMut(Entity, Entity) -> bool,
+) -> Vec<(Entity, Entity)> {
+    let candidates: HashSet<Entity> = ready.iter().map(|(entity, _)| *entity).collect();
+    let mut paired: HashSet<Entity> = HashSet::default();
+    let mut pairs = Vec::new();
+    for (entity, pos) in ready {
+        if paired.contains(entity) {
+            continue;
+        }
+        let partner = grid.nearest(*pos, radius, |other| {
+            other != *entity
+                && candidates.contains(&other)
+                && !paired.contains(&other)
+                && accept(*entity, other)
+        });
+        if let Some((partner, _)) = partner {
+            paired.insert(*entity);
+            paired.insert(partner);
+            pairs.push((*entity, partner));
+        }
+    }
+    pairs
+}
+
+/// Rasgos lo bastante parecidos y, con cerebros NEAT, de la misma especie.
+pub fn compatible(config: &SimConfig, a: &Genes, b: &Genes) -> bool {
+    let species = |genes: &Genes| genes.brain.as_ref().and_then(|brain| brain.neat()?.species);
+    a.distance(b, &config.genome) <= config.mating.max_genetic_distance && species(a) == species(b)
+}
+
+/// Los herbívoros en celo se dirigen hacia la pareja compatible más cercana que ven
+/// (con el alcance, el cono y el ruido de su `Perception`) a menos de `search_radius`.
+#[allow(clippy::type_complexity)]
+pub fn seek_mate_system(
+    config: Res<SimConfig>,
+    index: Res<SpatialIndex>,
+    mut sim_rng: ResMut<SimRng>,
+    mut creatures: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &State,
+            &Genes,
+            &Perception,
+        ),
+        With<Creature>,
+    >,
+) {
+    let in_heat: HashMap<Entity, Genes> = creatures
+        .iter()
+        .filter(|(.., state, _, _)| **state == State::Reproducing)
+        .map(|(entity, .., genes, _)| (entity, genes.clone()))
+        .collect();
+
+    let rng = sim_rng.stream(RngStream::Perception);
+    let search_radius = config.mating.search_radius;
+    for (entity, transform, mut velocity, state, genes, perception) in creatures.iter_mut() {
+        if *state != State::Reproducing || genes.brain.is_some() {
+            continue;
+        }
+        let pos = transform.translation.truncate();
+        let partner = look_where(
+            perception,
+            &index.creatures,
+            pos,
+            config.creatures.perception_noise,
+            rng,
+            |other, other_pos| {
+                other != entity
+                    && pos.distance(other_pos) < search_radius
+                    && in_heat
+                        .get(&other)
+                        .is_some_and(|other_genes| compatible(&config, genes, other_genes))
+            },
+        );
+        if let Some(partner) = partner {
+            let direction = (partner.position - pos).normalize_or_zero();
+            velocity.0 = direction * genes.speed() * config.creatures.seek_speed_factor;
+        }
+    }
+}
+
+/// Los depredadores listos para aparearse (véase [`predator_ready`]) se acercan al
+/// compañero más cercano que ven a menos de `mate_search_radius` y que también lo esté.
+#[allow(clippy::type_complexity)]
+pub fn predator_seek_mate_system(
+    config: Res<SimConfig>,
+    index: Res<SpatialIndex>,
+    mut sim_rng: ResMut<SimRng>,
+    mut predators: Query<(
+        Entity,
+        &Transform,
+        &mut Velocity,
+        &Organism,
+        &Predator,
+        &State,
+        &Genes,
+        &Perception,
+    )>,
+) {
+    let rng = sim_rng.stream(RngStream::Perception);
+    let predator_config = &config.predators;
+    let ready: HashSet<Entity> = predators
+        .iter()
+        .filter(|(_, _, _, organism, predator, state, ..)| {
+            predator_ready(predator_config, organism, predator, state)
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    for (entity, transform, mut velocity, .., genes, perception) in predators.iter_mut() {
+        if !ready.contains(&entity) {
+            continue;
+        }
+        let pos = transform.translation.truncate();
+        let search_radius = predator_config.mate_search_radius;
+        let partner = look_where(
+            perception,
+            &index.predators,
+            pos,
+            predator_config.perception_noise,
+            rng,
+            |other, other_pos| {
+                other != entity && ready.contains(&other) && pos.distance(other_pos) < search_radius
+            },
+        );
+        if let Some(partner) = partner {
+            velocity.0 = (partner.position - pos).normalize_or_zero() * genes.speed();
+        }
+    }
+}
+
+/// Un depredador tiene hambre, y por tanto caza, por debajo de `hunger_threshold`.
+pub fn predator_hungry(config: &PredatorConfig, organism: &Organism) -> bool {
+    organism.energy < config.hunger_threshold
+}
+
+/// Un depredador puede aparearse si está en temporada, no tiene hambre, terminó su
+/// espera y puede pagar el coste. El hambre manda: uno hambriento caza aunque esté
+/// en temporada.
+pub fn predator_ready(
+    config: &PredatorConfig,
+    organism: &Organism,
+    predator: &Predator,
+    state: &State,
+) -> bool {
+    *state == State::ReproducingSeason
+        && !predator_hungry(config, organism)
+        && predator.reproduction_cooldown <= 0.0
+        && organism.energy > config.reproduction_cost
+}
+
+/// Parejas compatibles a distancia de apareamiento tienen una cría en común.
+///
+/// Ambos progenitores pagan `reproduction_cost` y reinician su espera; la cría
+/// hereda el cruce de sus genes con mutación y nace entre los dos.
+#[allow(clippy::too_many_arguments)]
+pub fn mating_system(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    stats: Res<Stats>,
+    index: Res<SpatialIndex>,
+    mut sim_rng: ResMut<SimRng>,
+    mut lineage: ResMut<LineageStore>,
+    mut neat: ResMut<NeatState>,
+    mut births: EventWriter<BirthEvent>,
+    mut mutations: EventWriter<MutationEvent>,
+    mut query: Query<(
+        Entity,
+        &mut Creature,
+        &mut Organism,
+        &Transform,
+        &Velocity,
+        &Genes,
+        &State,
+    )>,
+) {
+    let rng = sim_rng.stream(RngStream::Reproduction);
+    let creatures = &config.creatures;
+
+    let ready: Vec<(Entity, Vec2)> = query
+        .iter()
+        .filter(|(_, creature, organism, _, _, genes, state)| {
+            organism.energy
+                > genes.get_or(Genes::REPRODUCTION_ENERGY, creatures.reproduction_energy)
+                && creature.time_since_reproduction > creatures.reproduction_cooldown
+                && (genes.brain.is_none() || **state == State::Reproducing)
+        })
+        .map(|(entity, _, _, transform, ..)| (entity, transform.translation.truncate()))
+        .collect();
+
+    let pairs = pair_nearby(
+        &ready,
+        &index.creatures,
+        config.mating.mate_radius,
+        |a, b| match (query.get(a), query.get(b)) {
+            (Ok((.., a_genes, _)), Ok((.., b_genes, _))) => compatible(&config, a_genes, b_genes),
+            _ => false,
+        },
+    );
+
+    let noise = creatures.child_velocity_noise;
+    for (a, b) in pairs {
+        let Ok([mut first, mut second]) = query.get_many_mut([a, b]) else {
+            continue;
+        };
+        for (_, creature, organism, ..) in [&mut first, &mut second] {
+            organism.energy -= creatures.reproduction_cost;
+            creature.time_since_reproduction = 0.0;
+        }
+
+        let child_gen = first.2.generation.max(second.2.generation) + 1;
+        let position = (first.3.translation + second.3.translation) / 2.0;
+        let heading = first.4.0 + second.4.0;
+        let vx = heading.x + rng.random_range(-noise..=noise);
+        let vy = heading.y + rng.random_range(-noise..=noise);
+        // El de más energía cuenta como el más apto para el cruce de cerebros NEAT.
+        let (fitter, other) = if first.2.energy >= second.2.energy {
+            (first.5, second.5)
+        } else {
+            (second.5, first.5)
+        };
+        let crossed = crossover_genes(fitter, other, rng);
+        let child_genes = inherit_genes(&config.genome, &crossed, &mut neat, rng);
+        let child_velocity = Vec2::new(vx, vy).normalize_or_zero() * child_genes.speed();
+        let child_id = lineage.register(
+            Species::Creature,
+            vec![first.2.id, second.2.id],
+            child_gen,
+            stats.simulation_time,
+            Some(&child_genes),
+        );
+
+        let child = spawn_child_creature(
+            &mut commands,
+            &config,
+            position + Vec3::new(10.0, 10.0, 0.0),
+            child_velocity,
+            child_genes.clone(),
+            child_id,
+            child_gen,
+        );
+
+        births.write(BirthEvent {
+            entity: child,
+            id: child_id,
+            species: Species::Creature,
+            parents: vec![a, b],
+            generation: child_gen,
+            genes: Some(child_genes.clone()),
+        });
+        mutations.write(MutationEvent {
+            entity: child,
+            parent: crossed,
+            child: child_genes,
+        });
+    }
+}
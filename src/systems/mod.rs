@@ -1,19 +1,51 @@
-pub mod movement;
-pub mod setup;
-pub mod states;
+pub mod boundaries;
+pub mod brain;
+pub mod clock;
 pub mod collisions;
+pub mod headless;
+pub mod hot_reload;
+pub mod hud;
+pub mod inspector;
+pub mod lineage;
+pub mod mating;
+pub mod movement;
+pub mod perception;
+pub mod pheromones;
+pub mod phylogeny;
 pub mod plant;
 pub mod predator;
+pub mod render;
 pub mod reproduction;
-pub mod hud;
-pub mod boundaries;
+pub mod setup;
+pub mod snapshot;
+pub mod spatial;
+pub mod speed;
+pub mod states;
+pub mod stats;
+pub mod stats_export;
 
-pub use movement::*;
-pub use setup::*;
-pub use states::*;
+pub use boundaries::*;
+pub use brain::*;
+pub use clock::*;
 pub use collisions::*;
+pub use headless::*;
+pub use hot_reload::*;
+pub use hud::*;
+pub use inspector::*;
+pub use lineage::*;
+pub use mating::*;
+pub use movement::*;
+pub use perception::*;
+pub use pheromones::*;
+pub use phylogeny::*;
 pub use plant::*;
 pub use predator::*;
+pub use render::*;
 pub use reproduction::*;
-pub use hud::*;
-pub use boundaries::*;
+pub use setup::*;
+pub use snapshot::*;
+pub use spatial::*;
+pub use speed::*;
+pub use states::*;
+pub use stats::*;
+pub use stats_export::*;
@@ -1,43 +1,81 @@
-use crate::components::{Creature, Organism, Predator, Velocity};
-use crate::resources::Stats;
+use crate::components::{Creature, Genes, Organism, Predator, Velocity};
+use crate::config::SimConfig;
+use crate::events::DeathEvent;
+use crate::resources::{DeathCause, Species};
 use bevy::prelude::*;
 
 /// Mueve criaturas y depredadores, aplica consumo de energía, envejecimiento y muerte.
+#[allow(clippy::type_complexity)]
 pub fn move_entities(
     time: Res<Time>,
+    config: Res<SimConfig>,
     mut commands: Commands,
-    mut stats: ResMut<Stats>,
+    mut deaths: EventWriter<DeathEvent>,
     mut params: ParamSet<(
-        Query<(Entity, &Velocity, &mut Transform, &mut Organism, &mut Creature)>,
-        Query<(Entity, &Velocity, &mut Transform, &mut Organism, &mut Predator)>,
+        Query<(
+            Entity,
+            &Velocity,
+            &mut Transform,
+            &mut Organism,
+            &mut Creature,
+            &Genes,
+        )>,
+        Query<(Entity, &Velocity, &mut Transform, &mut Organism, &Genes), With<Predator>>,
     )>,
 ) {
     // 🟢 Movimiento y lógica para criaturas (herbívoras)
-    for (entity, velocity, mut transform, mut organism, mut creature) in params.p0().iter_mut() {
+    for (entity, velocity, mut transform, mut organism, mut creature, genes) in
+        params.p0().iter_mut()
+    {
         transform.translation += velocity.0.extend(0.0) * time.delta_secs();
 
-        organism.energy -= 1.0 * time.delta_secs();
+        let drain = genes.metabolic_drain(&config.creatures, velocity.0.length());
+        organism.energy -= drain * time.delta_secs();
         organism.age += time.delta_secs();
         creature.time_since_reproduction += time.delta_secs();
 
         // Muerte por edad o agotamiento
-        if organism.energy <= 0.0 || organism.age > 60.0 {
+        let cause = if organism.energy <= 0.0 {
+            Some(DeathCause::Starvation)
+        } else if organism.age > genes.get_or(Genes::LIFESPAN, config.creatures.max_age) {
+            Some(DeathCause::OldAge)
+        } else {
+            None
+        };
+        if let Some(cause) = cause {
+            deaths.write(DeathEvent {
+                entity,
+                id: organism.id,
+                species: Species::Creature,
+                cause,
+                age: organism.age,
+                generation: organism.generation,
+                genes: Some(genes.clone()),
+            });
             commands.entity(entity).despawn();
-            stats.total_deaths += 1;
         }
     }
 
     // 🔴 Movimiento y lógica para depredadores
-    for (entity, velocity, mut transform, mut organism, _predator) in params.p1().iter_mut() {
+    for (entity, velocity, mut transform, mut organism, genes) in params.p1().iter_mut() {
         transform.translation += velocity.0.extend(0.0) * time.delta_secs();
 
-        organism.energy -= 0.8 * time.delta_secs();
+        let drain = genes.predator_drain(&config.predators, velocity.0.length());
+        organism.energy -= drain * time.delta_secs();
         organism.age += time.delta_secs();
 
         // Muerte por agotamiento
         if organism.energy <= 0.0 {
+            deaths.write(DeathEvent {
+                entity,
+                id: organism.id,
+                species: Species::Predator,
+                cause: DeathCause::Starvation,
+                age: organism.age,
+                generation: organism.generation,
+                genes: Some(genes.clone()),
+            });
             commands.entity(entity).despawn();
-            stats.total_deaths += 1;
         }
 
         // Nota: cooldown de reproducción se actualiza en otro sistema
@@ -0,0 +1,107 @@
+use crate::components::{Creature, Genes, Percept, Perception, Predator, Velocity};
+use crate::config::{MutationDistribution, SimConfig};
+use crate::resources::{RngStream, SimRng, SpatialIndex};
+use crate::utils::spatial::SpatialGrid;
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Actualiza lo que ve cada organismo: el objeto más cercano de cada tipo que quede
+/// dentro de su alcance y de su cono de visión, con ruido en la posición percibida.
+///
+/// Los herbívoros ven hasta `vision_range` y los depredadores hasta `hunt_radius`;
+/// ambos miran en la dirección en la que se movieron por última vez.
+#[allow(clippy::type_complexity)]
+pub fn perception_system(
+    config: Res<SimConfig>,
+    index: Res<SpatialIndex>,
+    mut sim_rng: ResMut<SimRng>,
+    mut creatures: Query<
+        (&Transform, &Velocity, &Genes, &mut Perception),
+        (With<Creature>, Without<Predator>),
+    >,
+    mut predators: Query<
+        (&Transform, &Velocity, &Genes, &mut Perception),
+        (With<Predator>, Without<Creature>),
+    >,
+) {
+    let rng = sim_rng.stream(RngStream::Perception);
+    let creature_config = &config.creatures;
+    for (transform, velocity, genes, mut perception) in creatures.iter_mut() {
+        let pos = transform.translation.truncate();
+        aim(
+            &mut perception,
+            velocity,
+            genes.get_or(Genes::VISION_RANGE, creature_config.vision_range),
+            genes.get_or(Genes::FIELD_OF_VIEW, creature_config.field_of_view),
+        );
+        let noise = creature_config.perception_noise;
+        perception.plant = look(&perception, &index.plants, pos, noise, rng);
+        perception.predator = look(&perception, &index.predators, pos, noise, rng);
+    }
+
+    let predator_config = &config.predators;
+    for (transform, velocity, genes, mut perception) in predators.iter_mut() {
+        let pos = transform.translation.truncate();
+        aim(
+            &mut perception,
+            velocity,
+            genes.get_or(Genes::HUNT_RADIUS, predator_config.hunt_radius),
+            genes.get_or(Genes::FIELD_OF_VIEW, predator_config.field_of_view),
+        );
+        perception.prey = look(
+            &perception,
+            &index.creatures,
+            pos,
+            predator_config.perception_noise,
+            rng,
+        );
+    }
+}
+
+/// Ajusta alcance, ángulo y orientación; un organismo parado sigue mirando hacia
+/// donde se movía.
+fn aim(perception: &mut Perception, velocity: &Velocity, range: f32, field_of_view: f32) {
+    perception.range = range;
+    perception.field_of_view = field_of_view.clamp(0.0, 360.0).to_radians();
+    if velocity.0 != Vec2::ZERO {
+        perception.heading = velocity.0.normalize();
+    }
+}
+
+/// Objeto visible más cercano de `grid`. Solo consume números aleatorios si hay ruido.
+fn look(
+    perception: &Perception,
+    grid: &SpatialGrid,
+    pos: Vec2,
+    noise: f32,
+    rng: &mut impl Rng,
+) -> Option<Percept> {
+    look_where(perception, grid, pos, noise, rng, |_, _| true)
+}
+
+/// Como [`look`], pero solo entre los objetos que `accept` dé por buenos; para
+/// búsquedas que dependen del estado de quien mira, como la de pareja.
+pub fn look_where(
+    perception: &Perception,
+    grid: &SpatialGrid,
+    pos: Vec2,
+    noise: f32,
+    rng: &mut impl Rng,
+    mut accept: impl FnMut(Entity, Vec2) -> bool,
+) -> Option<Percept> {
+    let (entity, actual) = grid.nearest_where(pos, perception.range, |other, target| {
+        perception.sees(pos, target) && accept(other, target)
+    })?;
+    let mut position = actual;
+    if noise > 0.0 {
+        let error = MutationDistribution::Gaussian {
+            sigma: noise * pos.distance(actual),
+        };
+        position += Vec2::new(error.sample(rng), error.sample(rng));
+    }
+    Some(Percept {
+        entity,
+        position,
+        distance: pos.distance(position),
+    })
+}
@@ -0,0 +1,174 @@
+use crate::components::{Creature, Plant, Predator};
+use crate::config::SimConfig;
+use crate::resources::{PheromoneField, WorldBounds};
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Difunde y desvanece los campos de olor y añade lo que emite cada organismo este tick.
+///
+/// Sin `pheromones` en la configuración los campos se vacían y no se actualizan.
+pub fn update_pheromones(
+    config: Res<SimConfig>,
+    time: Res<Time>,
+    bounds: Res<WorldBounds>,
+    mut field: ResMut<PheromoneField>,
+    plants: Query<(&Transform, &Plant)>,
+    creatures: Query<&Transform, With<Creature>>,
+    predators: Query<&Transform, With<Predator>>,
+) {
+    let Some(pheromones) = &config.pheromones else {
+        if !field.is_empty() {
+            *field = PheromoneField::default();
+        }
+        return;
+    };
+
+    if field.fit(&bounds, pheromones.cell_size) {
+        info!(
+            "👃 Campos de olor de {}x{} celdas",
+            field.food.width(),
+            field.food.height()
+        );
+    }
+
+    let dt = time.delta_secs();
+    let diffusion = pheromones.diffusion * dt;
+    let retention = (1.0 - pheromones.decay).powf(dt);
+    for channel in field.channels_mut() {
+        channel.step(diffusion, retention);
+    }
+
+    for (transform, plant) in plants.iter() {
+        let amount = pheromones.food_emission * plant.size * dt;
+        field.food.add(transform.translation.truncate(), amount);
+    }
+    for transform in predators.iter() {
+        field.danger.add(
+            transform.translation.truncate(),
+            pheromones.danger_emission * dt,
+        );
+    }
+    for transform in creatures.iter() {
+        field.trail.add(
+            transform.translation.truncate(),
+            pheromones.trail_emission * dt,
+        );
+    }
+}
+
+/// Dirección en la que huele más a comida desde `pos`, si el olor supera el umbral.
+pub fn food_scent(config: &SimConfig, field: &PheromoneField, pos: Vec2) -> Option<Vec2> {
+    let pheromones = config.pheromones.as_ref()?;
+    field.food.uphill(pos, pheromones.food_threshold)
+}
+
+/// Dirección en la que se aleja del olor a peligro desde `pos`, si este supera el umbral.
+pub fn danger_scent(config: &SimConfig, field: &PheromoneField, pos: Vec2) -> Option<Vec2> {
+    let pheromones = config.pheromones.as_ref()?;
+    field
+        .danger
+        .uphill(pos, pheromones.danger_threshold)
+        .map(|up| -up)
+}
+
+/// Capa que dibuja los campos de olor sobre el mundo.
+#[derive(Component)]
+pub struct PheromoneOverlay;
+
+/// Crea la capa de olores, oculta hasta pulsar `O`.
+pub fn setup_pheromone_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        Sprite::from_image(images.add(overlay_image(1, 1))),
+        // Por debajo de los organismos, que se dibujan en z = 0.
+        Transform::from_xyz(0.0, 0.0, -1.0),
+        Visibility::Hidden,
+        PheromoneOverlay,
+    ));
+}
+
+/// Muestra u oculta la capa de olores con la tecla `O`.
+pub fn toggle_pheromone_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlays: Query<&mut Visibility, With<PheromoneOverlay>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyO) {
+        return;
+    }
+    for mut visibility in overlays.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// Pinta los campos en la capa mientras está visible: rojo el peligro, verde la
+/// comida y azul el rastro, cada uno relativo a su máximo actual.
+pub fn update_pheromone_overlay(
+    field: Res<PheromoneField>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlays: Query<(&mut Sprite, &mut Transform, &Visibility), With<PheromoneOverlay>>,
+) {
+    for (mut sprite, mut transform, visibility) in overlays.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        let Some(image) = images.get_mut(&sprite.image) else {
+            continue;
+        };
+        let (width, height) = (field.food.width().max(1), field.food.height().max(1));
+        if image.width() as usize != width || image.height() as usize != height {
+            *image = overlay_image(width as u32, height as u32);
+        }
+        if let Some(data) = image.data.as_mut() {
+            paint(&field, width, data);
+        }
+
+        let (min, max) = field.food.extent();
+        sprite.custom_size = Some(max - min);
+        transform.translation = ((min + max) / 2.0).extend(transform.translation.z);
+    }
+}
+
+/// Escribe los píxeles RGBA; las filas de la imagen van de arriba abajo y las del
+/// campo de abajo arriba.
+fn paint(field: &PheromoneField, width: usize, data: &mut [u8]) {
+    data.fill(0);
+    if field.is_empty() {
+        return;
+    }
+    let channels = [&field.danger, &field.food, &field.trail];
+    let peaks = channels.map(|channel| channel.max());
+    for (row, pixels) in data.chunks_exact_mut(width * 4).rev().enumerate() {
+        for (column, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+            let i = row * width + column;
+            let mut strongest = 0.0f32;
+            for (c, channel) in channels.iter().enumerate() {
+                // La raíz realza los olores débiles frente a los focos.
+                let level = if peaks[c] > 0.0 {
+                    (channel.values()[i] / peaks[c]).sqrt()
+                } else {
+                    0.0
+                };
+                pixel[c] = (level * 255.0) as u8;
+                strongest = strongest.max(level);
+            }
+            pixel[3] = (strongest * 200.0) as u8;
+        }
+    }
+}
+
+fn overlay_image(width: u32, height: u32) -> Image {
+    Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    )
+}
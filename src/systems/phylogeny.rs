@@ -0,0 +1,391 @@
+use crate::components::{Genes, Organism};
+use crate::lineage::{LineageRecord, LineageStore, OrganismId};
+use crate::resources::{SelectedOrganism, Species, Stats};
+use crate::systems::render::PREDATOR_COLOR;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+const PANEL_WIDTH: f32 = 480.0;
+const CANVAS_HEIGHT: f32 = 300.0;
+/// Máximo de organismos dibujados; se priorizan los vivos más recientes.
+const MAX_TREE_NODES: usize = 600;
+/// Segundos reales entre redibujados del árbol mientras el panel está abierto.
+const REFRESH_INTERVAL: f32 = 1.0;
+const CONNECTOR_COLOR: Color = Color::srgba(0.7, 0.7, 0.7, 0.6);
+const SELECTED_COLOR: Color = Color::srgb(1.0, 1.0, 0.2);
+
+/// Raíz del panel genealógico.
+#[derive(Component)]
+pub struct PhylogenyPanel;
+
+/// Zona donde se dibujan las ramas del árbol.
+#[derive(Component)]
+pub struct PhylogenyCanvas;
+
+/// Texto con los datos del organismo seleccionado.
+#[derive(Component)]
+pub struct PhylogenyInfoText;
+
+/// Rama clicable de un organismo en el árbol.
+#[derive(Component)]
+pub struct PhylogenyNode(pub OrganismId);
+
+/// Crea el panel genealógico, oculto hasta pulsar `G`.
+pub fn setup_phylogeny_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands
+        .spawn((
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            PhylogenyPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("🌳 Genealogía de la población (G para cerrar)"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+            ));
+            panel.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(CANVAS_HEIGHT),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                PhylogenyCanvas,
+            ));
+            panel.spawn((
+                Text::new("Haz clic en una rama para ver el organismo"),
+                TextFont {
+                    font,
+                    font_size: 14.0,
+                    ..default()
+                },
+                PhylogenyInfoText,
+            ));
+        });
+}
+
+/// Muestra u oculta el panel con la tecla `G`.
+pub fn toggle_phylogeny_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut panels: Query<&mut Node, With<PhylogenyPanel>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+    for mut node in panels.iter_mut() {
+        node.display = match node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+/// Redibuja periódicamente el árbol de la población actual mientras el panel está visible.
+///
+/// El eje horizontal es el tiempo de simulación y cada organismo es una rama que
+/// va de su nacimiento a su muerte (o al presente), coloreada según sus genes.
+#[allow(clippy::too_many_arguments)]
+pub fn refresh_phylogeny_tree(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    stats: Res<Stats>,
+    lineage: Res<LineageStore>,
+    selected: Res<SelectedOrganism>,
+    panels: Query<&Node, With<PhylogenyPanel>>,
+    canvases: Query<(Entity, &ComputedNode), With<PhylogenyCanvas>>,
+    mut since_refresh: Local<Option<f32>>,
+) {
+    let visible = panels.iter().any(|node| node.display != Display::None);
+    if !visible {
+        // Al reabrir el panel se redibuja de inmediato.
+        *since_refresh = None;
+        return;
+    }
+    let elapsed = since_refresh.map_or(f32::INFINITY, |t| t + time.delta_secs());
+    if elapsed < REFRESH_INTERVAL && !selected.is_changed() {
+        *since_refresh = Some(elapsed);
+        return;
+    }
+    *since_refresh = Some(0.0);
+
+    let Ok((canvas, computed)) = canvases.single() else {
+        return;
+    };
+    let size = computed.size() * computed.inverse_scale_factor();
+    let width = if size.x > 0.0 {
+        size.x
+    } else {
+        PANEL_WIDTH - 16.0
+    };
+
+    let now = stats.simulation_time;
+    let rows = layout_tree(&lineage, now, MAX_TREE_NODES);
+    let start = rows.iter().map(|row| row.born).fold(now, f32::min);
+    let span = (now - start).max(f32::EPSILON);
+    let x_of = |t: f32| (t - start) / span * width;
+    let row_height = CANVAS_HEIGHT / rows.len().max(1) as f32;
+    let bar_height = (row_height * 0.7).clamp(2.0, 6.0);
+    let y_of = |row: usize| row as f32 * row_height + row_height / 2.0;
+
+    commands.entity(canvas).despawn_related::<Children>();
+    commands.entity(canvas).with_children(|canvas| {
+        for row in &rows {
+            let x = x_of(row.born);
+            let y = y_of(row.row);
+
+            if let Some(parent_row) = row.parent_row {
+                let parent_y = y_of(parent_row);
+                canvas.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(x),
+                        top: Val::Px(parent_y.min(y)),
+                        width: Val::Px(1.0),
+                        height: Val::Px((parent_y - y).abs()),
+                        ..default()
+                    },
+                    BackgroundColor(CONNECTOR_COLOR),
+                ));
+            }
+
+            let color = if selected.0 == Some(row.id) {
+                SELECTED_COLOR
+            } else {
+                row.color
+            };
+            canvas.spawn((
+                Button,
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(x),
+                    top: Val::Px(y - bar_height / 2.0),
+                    width: Val::Px((x_of(row.end) - x).max(2.0)),
+                    height: Val::Px(bar_height),
+                    ..default()
+                },
+                BackgroundColor(color),
+                PhylogenyNode(row.id),
+            ));
+        }
+    });
+}
+
+/// Al pulsar una rama se selecciona ese organismo.
+pub fn phylogeny_node_clicks(
+    nodes: Query<(&Interaction, &PhylogenyNode), Changed<Interaction>>,
+    mut selected: ResMut<SelectedOrganism>,
+) {
+    for (interaction, node) in nodes.iter() {
+        if *interaction == Interaction::Pressed {
+            selected.0 = Some(node.0);
+        }
+    }
+}
+
+/// Describe el organismo seleccionado: estado actual si vive, datos históricos si no.
+///
+/// Solo trabaja con el panel visible. El recuento de descendientes vivos recorre la
+/// ascendencia de toda la población, así que se guarda y solo se recalcula cuando
+/// cambian la selección o la genealogía.
+pub fn update_phylogeny_info(
+    selected: Res<SelectedOrganism>,
+    lineage: Res<LineageStore>,
+    organisms: Query<(&Organism, Option<&Genes>)>,
+    panels: Query<&Node, With<PhylogenyPanel>>,
+    mut texts: Query<&mut Text, With<PhylogenyInfoText>>,
+    mut descendants: Local<Option<(OrganismId, usize)>>,
+) {
+    let visible = panels.iter().any(|node| node.display != Display::None);
+    if !visible {
+        // Lo que cambie con el panel cerrado no se detecta; al reabrirlo se recalcula.
+        *descendants = None;
+        return;
+    }
+    let Some(id) = selected.0 else {
+        return;
+    };
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+    let Some(record) = lineage.get(id) else {
+        *text = Text::new(format!("#{id}: sin registro genealógico"));
+        return;
+    };
+
+    if selected.is_changed()
+        || lineage.is_changed()
+        || descendants.is_none_or(|(cached, _)| cached != id)
+    {
+        *descendants = Some((id, living_descendants(&lineage, id)));
+    }
+    let living = descendants.map_or(0, |(_, count)| count);
+
+    let header = format!(
+        "#{} {} · Gen {} · nacido a los {:.1}s · {living} descendientes vivos",
+        record.id, record.species, record.generation, record.born_at,
+    );
+    let detail = match organisms.iter().find(|(organism, _)| organism.id == id) {
+        Some((organism, genes)) => {
+            let genes = genes
+                .map(|g| describe_traits(&g.traits))
+                .unwrap_or_default();
+            format!(
+                "🟢 Vivo · energía {:.1} · edad {:.1}s{genes}",
+                organism.energy, organism.age
+            )
+        }
+        None => {
+            let death = match (record.died_at, record.death_cause) {
+                (Some(time), Some(cause)) => {
+                    format!(
+                        "murió a los {:.1}s ({cause}), con {:.1}s de edad",
+                        time,
+                        time - record.born_at
+                    )
+                }
+                _ => "sin datos de su muerte".to_string(),
+            };
+            let genes = record
+                .genes
+                .as_ref()
+                .map(|g| describe_traits(&g.traits))
+                .unwrap_or_default();
+            format!("💀 Extinto · {death}{genes}")
+        }
+    };
+    *text = Text::new(format!("{header}\n{detail}"));
+}
+
+/// Marca en el mundo al organismo seleccionado, si sigue vivo.
+pub fn highlight_selected_organism(
+    selected: Res<SelectedOrganism>,
+    organisms: Query<(&Organism, &Transform, Option<&Genes>)>,
+    mut gizmos: Gizmos,
+) {
+    let Some(id) = selected.0 else {
+        return;
+    };
+    if let Some((_, transform, genes)) = organisms.iter().find(|(organism, ..)| organism.id == id) {
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            selection_radius(genes),
+            SELECTED_COLOR,
+        );
+    }
+}
+
+/// Radio del círculo de selección, algo mayor que el sprite del organismo.
+pub fn selection_radius(genes: Option<&Genes>) -> f32 {
+    genes.map_or(25.0, Genes::size) * 0.75 + 6.0
+}
+
+/// Fila del árbol: una rama horizontal por organismo.
+struct TreeRow {
+    id: OrganismId,
+    row: usize,
+    parent_row: Option<usize>,
+    born: f32,
+    end: f32,
+    color: Color,
+}
+
+/// Coloca en filas a los organismos vivos y a sus antepasados por línea del
+/// primer progenitor, en preorden para que cada linaje quede agrupado.
+///
+/// Nunca pasa de `max_nodes` filas: si se alcanza a mitad de una línea de
+/// antepasados, los más antiguos quedan fuera y la rama empieza como raíz.
+fn layout_tree(lineage: &LineageStore, now: f32, max_nodes: usize) -> Vec<TreeRow> {
+    let mut included = BTreeSet::new();
+    let mut living: Vec<&LineageRecord> = lineage.living().collect();
+    living.sort_unstable_by_key(|record| std::cmp::Reverse(record.id));
+    'living: for record in living {
+        let mut current = Some(record);
+        while let Some(node) = current {
+            if included.len() >= max_nodes {
+                break 'living;
+            }
+            if !included.insert(node.id) {
+                break;
+            }
+            current = node.parents.first().and_then(|parent| lineage.get(*parent));
+        }
+    }
+
+    let mut children: HashMap<OrganismId, Vec<OrganismId>> = HashMap::default();
+    let mut roots = Vec::new();
+    for id in &included {
+        match lineage.get(*id).and_then(|record| record.parents.first()) {
+            Some(parent) if included.contains(parent) => {
+                children.entry(*parent).or_default().push(*id);
+            }
+            _ => roots.push(*id),
+        }
+    }
+
+    let mut rows = Vec::with_capacity(included.len());
+    let mut pending: Vec<(OrganismId, Option<usize>)> =
+        roots.into_iter().rev().map(|id| (id, None)).collect();
+    while let Some((id, parent_row)) = pending.pop() {
+        let Some(record) = lineage.get(id) else {
+            continue;
+        };
+        let row = rows.len();
+        rows.push(TreeRow {
+            id,
+            row,
+            parent_row,
+            born: record.born_at,
+            end: record.died_at.unwrap_or(now),
+            color: record_color(record),
+        });
+        if let Some(kids) = children.get(&id) {
+            pending.extend(kids.iter().rev().map(|kid| (*kid, Some(row))));
+        }
+    }
+    rows
+}
+
+fn record_color(record: &LineageRecord) -> Color {
+    let color = match (&record.genes, record.species) {
+        (_, Species::Predator) => PREDATOR_COLOR,
+        (Some(genes), Species::Creature) => Genes::from(genes).color,
+        (None, Species::Creature) => Color::WHITE,
+    };
+    if record.is_alive() {
+        color
+    } else {
+        color.with_alpha(0.35)
+    }
+}
+
+/// Rasgos del genoma como " · nombre valor", en orden alfabético.
+fn describe_traits(traits: &BTreeMap<String, f32>) -> String {
+    traits
+        .iter()
+        .map(|(name, value)| format!(" · {name} {value:.1}"))
+        .collect()
+}
+
+fn living_descendants(lineage: &LineageStore, id: OrganismId) -> usize {
+    lineage
+        .living()
+        .filter(|record| record.id != id && lineage.ancestry(record.id).contains(&id))
+        .count()
+}
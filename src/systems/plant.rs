@@ -1,28 +1,27 @@
+use crate::components::{Creature, Genes, Organism, Plant, State};
+use crate::config::SimConfig;
+use crate::events::FeedEvent;
+use crate::resources::{RngStream, SimRng, SpatialIndex, Species};
+use bevy::platform::collections::HashSet;
 use bevy::prelude::*;
-use crate::components::{Plant, Organism, Creature};
 use rand::prelude::*;
 
 /// Spawnea algunas plantas al inicio.
-pub fn spawn_initial_plants(commands: &mut Commands) {
-    let mut rng = rand::rng();
-    for _ in 0..30 {
+pub fn spawn_initial_plants(commands: &mut Commands, config: &SimConfig, rng: &mut impl Rng) {
+    let population = &config.population;
+    for _ in 0..population.initial_plants {
         commands.spawn((
-            Sprite {
-                color: Color::srgb(0.1, 0.7, 0.1),
-                custom_size: Some(Vec2::splat(10.0)),
-                ..default()
-            },
             Transform::from_xyz(
-                rng.random_range(-300.0..=300.0),
-                rng.random_range(-200.0..=200.0),
+                rng.random_range(-population.spawn_half_width..=population.spawn_half_width),
+                rng.random_range(-population.spawn_half_height..=population.spawn_half_height),
                 0.0,
             ),
             GlobalTransform::default(),
             Visibility::Visible,
             Plant {
                 age: 0.0,
-                max_age: rng.random_range(30.0..60.0),
-                size: 10.0,
+                max_age: config.plants.lifespan.sample(rng),
+                size: config.plants.initial_size,
                 reproduction_timer: 0.0,
             },
         ));
@@ -33,16 +32,18 @@ pub fn spawn_initial_plants(commands: &mut Commands) {
 pub fn plant_growth_and_reproduction_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Plant, &mut Sprite, &Transform)>,
+    config: Res<SimConfig>,
+    mut sim_rng: ResMut<SimRng>,
+    mut query: Query<(Entity, &mut Plant, &Transform)>,
 ) {
-    let mut rng = rand::rng();
-    for (entity, mut plant, mut sprite, transform) in query.iter_mut() {
+    let rng = sim_rng.stream(RngStream::Plants);
+    let plants = &config.plants;
+    for (entity, mut plant, transform) in query.iter_mut() {
         plant.age += time.delta_secs();
         plant.reproduction_timer += time.delta_secs();
 
-        if plant.size < 15.0 {
-            plant.size += 2.0 * time.delta_secs();
-            sprite.custom_size = Some(Vec2::splat(plant.size));
+        if plant.size < plants.max_size {
+            plant.size += plants.growth_rate * time.delta_secs();
         }
 
         if plant.age > plant.max_age {
@@ -50,26 +51,21 @@ pub fn plant_growth_and_reproduction_system(
             continue;
         }
 
-        if plant.reproduction_timer >= 12.0 {
+        if plant.reproduction_timer >= plants.reproduction_interval {
             plant.reproduction_timer = 0.0;
             let offset = Vec3::new(
-                rng.random_range(-50.0..=50.0),
-                rng.random_range(-50.0..=50.0),
+                rng.random_range(-plants.seed_spread..=plants.seed_spread),
+                rng.random_range(-plants.seed_spread..=plants.seed_spread),
                 0.0,
             );
             commands.spawn((
-                Sprite {
-                    color: Color::srgb(0.1, 0.7, 0.1),
-                    custom_size: Some(Vec2::splat(10.0)),
-                    ..default()
-                },
                 Transform::from_translation(transform.translation + offset),
                 GlobalTransform::default(),
                 Visibility::Visible,
                 Plant {
                     age: 0.0,
-                    max_age: rng.random_range(30.0..60.0),
-                    size: 10.0,
+                    max_age: plants.lifespan.sample(rng),
+                    size: plants.initial_size,
                     reproduction_timer: 0.0,
                 },
             ));
@@ -79,21 +75,39 @@ pub fn plant_growth_and_reproduction_system(
 
 pub fn herbivore_plant_collision_system(
     mut commands: Commands,
-    mut query: Query<(Entity, &Transform, &mut Organism), With<Creature>>,
-    plants: Query<(Entity, &Transform), With<Plant>>,
+    config: Res<SimConfig>,
+    index: Res<SpatialIndex>,
+    mut feeds: EventWriter<FeedEvent>,
+    mut query: Query<(Entity, &Transform, &mut Organism, &Genes, &State), With<Creature>>,
+    plants: Query<(), With<Plant>>,
+    mut eaten: Local<HashSet<Entity>>,
 ) {
-    for (_creature_entity, creature_transform, mut organism) in query.iter_mut() {
-        for (plant_entity, plant_transform) in plants.iter() {
-            let distance = creature_transform
-                .translation
-                .truncate()
-                .distance(plant_transform.translation.truncate());
+    eaten.clear();
+
+    for (creature, creature_transform, mut organism, genes, state) in query.iter_mut() {
+        // Un herbívoro con cerebro solo come si su red lo decide.
+        if genes.brain.is_some() && *state != State::SeekingFood {
+            continue;
+        }
+        let pos = creature_transform.translation.truncate();
+
+        // Plantas aún vivas y no comidas por otra criatura en este tick
+        let plant = index
+            .plants
+            .within(pos, genes.eat_radius(&config.creatures))
+            .map(|(entity, _)| entity)
+            .find(|entity| plants.contains(*entity) && !eaten.contains(entity));
 
-            if distance < 10.0 {
-                organism.energy += 40.0;
-                commands.entity(plant_entity).despawn();
-                break;
-            }
+        if let Some(plant_entity) = plant {
+            organism.energy += config.plants.energy_value;
+            commands.entity(plant_entity).despawn();
+            eaten.insert(plant_entity);
+            feeds.write(FeedEvent {
+                eater: creature,
+                species: Species::Creature,
+                food: plant_entity,
+                energy_gained: config.plants.energy_value,
+            });
         }
     }
 }
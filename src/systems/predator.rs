@@ -1,67 +1,93 @@
-use crate::components::{Creature, Genes, Organism, Predator, State, Velocity};
-use crate::resources::Stats;
-use bevy::prelude::*;
+use crate::components::{Creature, Genes, Organism, Perception, Predator, Velocity};
+use crate::config::SimConfig;
+use crate::events::{DeathEvent, FeedEvent};
+use crate::resources::{DeathCause, Species};
+use crate::systems::mating::predator_hungry;
+use bevy::{platform::collections::HashSet, prelude::*};
 
-/// Sistema de caza: los depredadores buscan presas si tienen hambre y están en temporada de reproducción.
+/// Sistema de caza: los depredadores persiguen a la presa que ven cuando tienen hambre.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn predator_hunting_system(
     mut commands: Commands,
-    mut stats: ResMut<Stats>,
-    mut predators: Query<(
-        &Transform,
-        &mut Velocity,
-        &mut Organism,
-        &Predator,
-        &State,
-    )>,
-    creatures: Query<(Entity, &Transform, &Genes), With<Creature>>,
+    time: Res<Time>,
+    config: Res<SimConfig>,
+    mut deaths: EventWriter<DeathEvent>,
+    mut feeds: EventWriter<FeedEvent>,
+    mut predators: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut Organism,
+            &mut Predator,
+            &Genes,
+            &Perception,
+        ),
+        Without<Creature>,
+    >,
+    creatures: Query<(&Organism, &Genes, &Transform), With<Creature>>,
+    mut eaten: Local<HashSet<Entity>>,
 ) {
-    for (pred_transform, mut velocity, mut predator_org, _predator, state) in predators.iter_mut() {
-        // Solo cazan si tienen hambre y están en temporada de reproducción
-        if *state != State::ReproducingSeason || predator_org.energy >= 80.0 {
+    eaten.clear();
+    let predator_config = &config.predators;
+    for (predator, pred_transform, mut velocity, mut predator_org, mut hunter, genes, perception) in
+        predators.iter_mut()
+    {
+        // Solo cazan si tienen hambre
+        if !predator_hungry(predator_config, &predator_org) {
+            hunter.grip = 0.0;
             continue;
         }
 
-        // Busca la criatura más cercana dentro del rango
-        if let Some((closest_entity, closest_transform, _genes)) = creatures
-            .iter()
-            .filter(|(_, transform, _)| {
-                pred_transform
-                    .translation
-                    .truncate()
-                    .distance(transform.translation.truncate())
-                    < 150.0
-            })
-            .min_by(|(_, a, _), (_, b, _)| {
-                let da = pred_transform
-                    .translation
-                    .truncate()
-                    .distance_squared(a.translation.truncate());
-                let db = pred_transform
-                    .translation
-                    .truncate()
-                    .distance_squared(b.translation.truncate());
-                da.total_cmp(&db)
-            })
-        {
-            // Dirección hacia la presa
-            let direction = (closest_transform.translation - pred_transform.translation)
-                .truncate()
-                .normalize_or_zero();
-            velocity.0 = direction * 90.0;
+        // Persigue a la presa más cercana que ve, si sigue viva
+        let pred_pos = pred_transform.translation.truncate();
+        let prey = perception
+            .prey
+            .filter(|prey| creatures.contains(prey.entity) && !eaten.contains(&prey.entity));
+        if let Some(prey) = prey {
+            // Corre hacia donde cree que está la presa
+            let closest_entity = prey.entity;
+            let direction = (prey.position - pred_pos).normalize_or_zero();
+            velocity.0 = direction * genes.speed() * predator_config.hunt_speed_factor;
 
-            // Si está lo suficientemente cerca, devora a la presa
-            let distance = pred_transform
-                .translation
-                .truncate()
-                .distance(closest_transform.translation.truncate());
+            // Si está lo suficientemente cerca la sujeta; las presas grandes
+            // aguantan más y pueden zafarse huyendo fuera del radio de ataque.
+            let Ok((prey_org, prey_genes, prey_transform)) = creatures.get(closest_entity) else {
+                continue;
+            };
+            let distance = pred_pos.distance(prey_transform.translation.truncate());
+            if distance >= genes.get_or(Genes::ATTACK_RADIUS, predator_config.attack_radius) {
+                hunter.grip = 0.0;
+                continue;
+            }
+            hunter.grip += time.delta_secs();
 
-            if distance < 25.0 {
+            if hunter.grip >= prey_genes.grip_time(predator_config) {
+                hunter.grip = 0.0;
+                deaths.write(DeathEvent {
+                    entity: closest_entity,
+                    id: prey_org.id,
+                    species: Species::Creature,
+                    cause: DeathCause::Predation,
+                    age: prey_org.age,
+                    generation: prey_org.generation,
+                    genes: Some(prey_genes.clone()),
+                });
                 commands.entity(closest_entity).despawn();
-                predator_org.energy = (predator_org.energy + 40.0).min(150.0); // Recupera energía
-                stats.total_deaths += 1;
+                eaten.insert(closest_entity);
+                let energy_before = predator_org.energy;
+                predator_org.energy = (predator_org.energy + predator_config.prey_energy)
+                    .min(predator_config.max_energy); // Recupera energía
+                feeds.write(FeedEvent {
+                    eater: predator,
+                    species: Species::Predator,
+                    food: closest_entity,
+                    energy_gained: predator_org.energy - energy_before,
+                });
             }
         } else {
             // No hay presas cerca: queda inmóvil
+            hunter.grip = 0.0;
             velocity.0 = Vec2::ZERO;
         }
     }
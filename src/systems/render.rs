@@ -0,0 +1,63 @@
+use crate::components::{Creature, Genes, Plant, Predator};
+use bevy::prelude::*;
+
+pub const PREDATOR_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
+const PLANT_COLOR: Color = Color::srgb(0.1, 0.7, 0.1);
+
+/// Cámara 2D del modo con ventana.
+pub fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+/// Añade un sprite a las criaturas nuevas según sus genes.
+#[allow(clippy::type_complexity)]
+pub fn attach_creature_sprites(
+    mut commands: Commands,
+    query: Query<(Entity, &Genes), (With<Creature>, Without<Sprite>)>,
+) {
+    for (entity, genes) in query.iter() {
+        commands.entity(entity).insert(Sprite {
+            color: genes.color,
+            custom_size: Some(Vec2::splat(genes.size())),
+            ..default()
+        });
+    }
+}
+
+/// Añade un sprite a los depredadores nuevos, del tamaño que dicten sus genes.
+///
+/// Se dibujan siempre en rojo para distinguirlos de los herbívoros.
+#[allow(clippy::type_complexity)]
+pub fn attach_predator_sprites(
+    mut commands: Commands,
+    query: Query<(Entity, &Genes), (With<Predator>, Without<Sprite>)>,
+) {
+    for (entity, genes) in query.iter() {
+        commands.entity(entity).insert(Sprite {
+            color: PREDATOR_COLOR,
+            custom_size: Some(Vec2::splat(genes.size())),
+            ..default()
+        });
+    }
+}
+
+/// Añade un sprite a las plantas nuevas.
+pub fn attach_plant_sprites(
+    mut commands: Commands,
+    query: Query<(Entity, &Plant), Without<Sprite>>,
+) {
+    for (entity, plant) in query.iter() {
+        commands.entity(entity).insert(Sprite {
+            color: PLANT_COLOR,
+            custom_size: Some(Vec2::splat(plant.size)),
+            ..default()
+        });
+    }
+}
+
+/// Refleja el crecimiento de las plantas en el tamaño de su sprite.
+pub fn sync_plant_sprites(mut query: Query<(&Plant, &mut Sprite), Changed<Plant>>) {
+    for (plant, mut sprite) in query.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(plant.size));
+    }
+}
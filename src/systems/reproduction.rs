@@ -1,69 +1,183 @@
 use crate::{
     components::{Creature, Genes, Organism, Predator, State, Velocity},
-    resources::Stats,
-    utils::factory::{spawn_child_creature, spawn_child_predator},
+    config::SimConfig,
+    events::{BirthEvent, MutationEvent, StateChangedEvent},
+    lineage::LineageStore,
+    neat::NeatState,
+    resources::{RngStream, SimRng, SpatialIndex, Species, Stats},
+    systems::mating::{pair_nearby, predator_ready},
+    utils::factory::{crossover_genes, inherit_genes, spawn_child_creature, spawn_child_predator},
 };
 use bevy::prelude::*;
 use rand::prelude::*;
 
 /// Las criaturas se reproducen si tienen suficiente energía y tiempo desde la última reproducción.
+#[allow(clippy::too_many_arguments)]
 pub fn reproduction_system(
     mut commands: Commands,
-    mut stats: ResMut<Stats>,
-    mut query: Query<(&mut Creature, &mut Organism, &Transform, &Velocity, &Genes)>,
+    config: Res<SimConfig>,
+    stats: Res<Stats>,
+    mut sim_rng: ResMut<SimRng>,
+    mut lineage: ResMut<LineageStore>,
+    mut neat: ResMut<NeatState>,
+    mut births: EventWriter<BirthEvent>,
+    mut mutations: EventWriter<MutationEvent>,
+    mut query: Query<(
+        Entity,
+        &mut Creature,
+        &mut Organism,
+        &Transform,
+        &Velocity,
+        &Genes,
+        &State,
+    )>,
 ) {
-    let mut rng = rand::rng();
-
-    for (mut creature, mut organism, transform, velocity, genes) in query.iter_mut() {
-        if organism.energy > 120.0 && creature.time_since_reproduction > 5.0 {
-            organism.energy -= 40.0;
+    let rng = sim_rng.stream(RngStream::Reproduction);
+    let creatures = &config.creatures;
+    let noise = creatures.child_velocity_noise;
+
+    for (parent, mut creature, mut organism, transform, velocity, genes, state) in query.iter_mut()
+    {
+        // Un herbívoro con cerebro solo se reproduce si su red lo decide.
+        if genes.brain.is_some() && *state != State::Reproducing {
+            continue;
+        }
+        let reproduction_energy =
+            genes.get_or(Genes::REPRODUCTION_ENERGY, creatures.reproduction_energy);
+        if organism.energy > reproduction_energy
+            && creature.time_since_reproduction > creatures.reproduction_cooldown
+        {
+            organism.energy -= creatures.reproduction_cost;
             creature.time_since_reproduction = 0.0;
 
             let child_gen = organism.generation + 1;
-            let vx = velocity.0.x + rng.random_range(-5.0..=5.0);
-            let vy = velocity.0.y + rng.random_range(-5.0..=5.0);
+            let vx = velocity.0.x + rng.random_range(-noise..=noise);
+            let vy = velocity.0.y + rng.random_range(-noise..=noise);
+            let child_genes = inherit_genes(&config.genome, genes, &mut neat, rng);
+            // La cría sale en la dirección del progenitor, pero a su propia velocidad.
+            let child_velocity = Vec2::new(vx, vy).normalize_or_zero() * child_genes.speed();
+            let child_id = lineage.register(
+                Species::Creature,
+                vec![organism.id],
+                child_gen,
+                stats.simulation_time,
+                Some(&child_genes),
+            );
 
-            spawn_child_creature(
+            let child = spawn_child_creature(
                 &mut commands,
+                &config,
                 transform.translation + Vec3::new(10.0, 10.0, 0.0),
-                Vec2::new(vx, vy),
-                genes,
+                child_velocity,
+                child_genes.clone(),
+                child_id,
                 child_gen,
             );
 
-            stats.total_reproductions += 1;
-            stats.max_generation = stats.max_generation.max(child_gen);
+            births.write(BirthEvent {
+                entity: child,
+                id: child_id,
+                species: Species::Creature,
+                parents: vec![parent],
+                generation: child_gen,
+                genes: Some(child_genes.clone()),
+            });
+            mutations.write(MutationEvent {
+                entity: child,
+                parent: genes.clone(),
+                child: child_genes,
+            });
         }
     }
 }
 
-/// Los depredadores se reproducen si hay al menos dos, están en temporada, y su cooldown terminó.
+/// Dos depredadores en temporada y a distancia de apareamiento tienen una cría en común.
+///
+/// Ambos pagan `reproduction_cost` y reinician su espera; la cría hereda el cruce
+/// de sus genes con mutación y nace entre los dos.
+#[allow(clippy::too_many_arguments)]
 pub fn predator_reproduction_system(
     mut commands: Commands,
-    mut query: Query<(&Transform, &Velocity, &mut Predator, &mut Organism, &State)>,
+    config: Res<SimConfig>,
+    stats: Res<Stats>,
+    index: Res<SpatialIndex>,
+    mut sim_rng: ResMut<SimRng>,
+    mut lineage: ResMut<LineageStore>,
+    mut neat: ResMut<NeatState>,
+    mut births: EventWriter<BirthEvent>,
+    mut mutations: EventWriter<MutationEvent>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &Velocity,
+        &mut Predator,
+        &mut Organism,
+        &State,
+        &Genes,
+    )>,
 ) {
-    let predators: Vec<_> = query.iter_mut().collect();
-    let mut spawned = 0;
-
-    if predators.len() >= 2 {
-        for (transform, velocity, mut predator, organism, state) in predators {
-            if *state == State::ReproducingSeason
-                && predator.reproduction_cooldown <= 0.0
-                && spawned < 1
-            {
-                let new_gen = organism.generation + 1;
-
-                spawn_child_predator(
-                    &mut commands,
-                    transform.translation + Vec3::new(10.0, 10.0, 0.0),
-                    velocity.0,
-                    new_gen,
-                );
-
-                predator.reproduction_cooldown = 10.0;
-                spawned += 1;
-            }
+    let rng = sim_rng.stream(RngStream::Reproduction);
+    let predator_config = &config.predators;
+
+    let ready: Vec<(Entity, Vec2)> = query
+        .iter()
+        .filter(|(_, _, _, predator, organism, state, _)| {
+            predator_ready(predator_config, organism, predator, state)
+        })
+        .map(|(entity, transform, ..)| (entity, transform.translation.truncate()))
+        .collect();
+    let pairs = pair_nearby(
+        &ready,
+        &index.predators,
+        predator_config.mate_radius,
+        |_, _| true,
+    );
+
+    for (a, b) in pairs {
+        let Ok([mut first, mut second]) = query.get_many_mut([a, b]) else {
+            continue;
+        };
+        for (_, _, _, predator, organism, ..) in [&mut first, &mut second] {
+            organism.energy -= predator_config.reproduction_cost;
+            predator.reproduction_cooldown = predator_config.reproduction_cooldown;
         }
+
+        let new_gen = first.4.generation.max(second.4.generation) + 1;
+        let position = (first.1.translation + second.1.translation) / 2.0;
+        let crossed = crossover_genes(first.6, second.6, rng);
+        let child_genes = inherit_genes(&predator_config.genome, &crossed, &mut neat, rng);
+        let child_velocity = (first.2.0 + second.2.0).normalize_or_zero() * child_genes.speed();
+        let child_id = lineage.register(
+            Species::Predator,
+            vec![first.4.id, second.4.id],
+            new_gen,
+            stats.simulation_time,
+            Some(&child_genes),
+        );
+
+        let child = spawn_child_predator(
+            &mut commands,
+            &config,
+            position + Vec3::new(10.0, 10.0, 0.0),
+            child_velocity,
+            child_genes.clone(),
+            child_id,
+            new_gen,
+        );
+
+        births.write(BirthEvent {
+            entity: child,
+            id: child_id,
+            species: Species::Predator,
+            parents: vec![a, b],
+            generation: new_gen,
+            genes: Some(child_genes.clone()),
+        });
+        mutations.write(MutationEvent {
+            entity: child,
+            parent: crossed,
+            child: child_genes,
+        });
     }
 }
 
@@ -75,32 +189,60 @@ pub fn update_predator_cooldowns(time: Res<Time>, mut query: Query<&mut Predator
 }
 
 /// Decide si los depredadores están en temporada de reproducción.
-pub fn update_predator_states(mut query: Query<(&mut State, &Organism), With<Predator>>) {
-    for (mut state, organism) in query.iter_mut() {
-        let new_state = if organism.energy > 110.0 {
+pub fn update_predator_states(
+    config: Res<SimConfig>,
+    mut changes: EventWriter<StateChangedEvent>,
+    mut query: Query<(Entity, &mut State, &Organism), With<Predator>>,
+) {
+    for (entity, mut state, organism) in query.iter_mut() {
+        let new_state = if organism.energy > config.predators.season_energy {
             State::ReproducingSeason
         } else {
             State::Wandering
         };
 
-        *state = new_state;
+        if *state != new_state {
+            changes.write(StateChangedEvent {
+                entity,
+                species: Species::Predator,
+                from: *state,
+                to: new_state,
+            });
+            *state = new_state;
+        }
     }
 }
 
-/// Spawnea criaturas iniciales al comenzar la simulación.
-pub fn spawn_initial_creatures(commands: &mut Commands) {
-    use crate::utils::factory::spawn_creature;
+/// Spawnea criaturas iniciales al comenzar la simulación; son los fundadores de sus linajes.
+pub fn spawn_initial_creatures(
+    commands: &mut Commands,
+    config: &SimConfig,
+    lineage: &mut LineageStore,
+    neat: &mut NeatState,
+    rng: &mut impl Rng,
+) {
+    use crate::utils::factory::{initial_genes, spawn_creature};
 
-    for _ in 0..10 {
-        spawn_creature(commands, 0);
+    for _ in 0..config.population.initial_creatures {
+        let genes = initial_genes(&config.genome, neat, rng);
+        let id = lineage.register(Species::Creature, Vec::new(), 0, 0.0, Some(&genes));
+        spawn_creature(commands, config, rng, genes, id, 0);
     }
 }
 
 /// Spawnea depredadores iniciales.
-pub fn spawn_initial_predators(commands: &mut Commands) {
-    use crate::utils::factory::spawn_predator;
+pub fn spawn_initial_predators(
+    commands: &mut Commands,
+    config: &SimConfig,
+    lineage: &mut LineageStore,
+    neat: &mut NeatState,
+    rng: &mut impl Rng,
+) {
+    use crate::utils::factory::{initial_genes, spawn_predator};
 
-    for _ in 0..2 {
-        spawn_predator(commands, 0);
+    for _ in 0..config.population.initial_predators {
+        let genes = initial_genes(&config.predators.genome, neat, rng);
+        let id = lineage.register(Species::Predator, Vec::new(), 0, 0.0, Some(&genes));
+        spawn_predator(commands, config, rng, genes, id, 0);
     }
 }
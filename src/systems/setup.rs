@@ -1,5 +1,8 @@
 use crate::{
-    components::FpsText,
+    config::SimConfig,
+    lineage::LineageStore,
+    neat::NeatState,
+    resources::{RngStream, SimRng},
     systems::{
         plant::spawn_initial_plants,
         reproduction::{spawn_initial_creatures, spawn_initial_predators},
@@ -7,47 +10,18 @@ use crate::{
 };
 use bevy::prelude::*;
 
-pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Cámara
-    commands.spawn(Camera2d);
+/// Spawnea la población inicial del ecosistema.
+pub fn setup(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    mut sim_rng: ResMut<SimRng>,
+    mut lineage: ResMut<LineageStore>,
+    mut neat: ResMut<NeatState>,
+) {
+    info!("🎲 Semilla de simulación: {}", sim_rng.seed());
 
-    // FPS Text
-    commands
-        .spawn((
-            Text::new("FPS: "),
-            TextFont {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 30.0,
-                ..default()
-            },
-        ))
-        .with_child((
-            TextSpan::from(""),
-            TextFont {
-                font_size: 24.0,
-                ..default()
-            },
-            FpsText,
-        ));
-
-    // HUD general
-    commands.spawn((
-        Text::new(""),
-        TextFont {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-            font_size: 20.0,
-            ..default()
-        },
-        Node {
-            position_type: PositionType::Absolute,
-            top: Val::Px(5.0),
-            left: Val::Px(10.0),
-            ..default()
-        },
-    ));
-
-    // Spawns iniciales
-    spawn_initial_creatures(&mut commands);
-    spawn_initial_predators(&mut commands);
-    spawn_initial_plants(&mut commands);
+    let rng = sim_rng.stream(RngStream::Setup);
+    spawn_initial_creatures(&mut commands, &config, &mut lineage, &mut neat, rng);
+    spawn_initial_predators(&mut commands, &config, &mut lineage, &mut neat, rng);
+    spawn_initial_plants(&mut commands, &config, rng);
 }
@@ -0,0 +1,85 @@
+use crate::resources::SnapshotSettings;
+use crate::snapshot::WorldSnapshot;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Petición de guardar o cargar una instantánea del ecosistema.
+#[derive(Event, Clone, Debug)]
+pub enum SnapshotRequest {
+    Save(PathBuf),
+    Load(PathBuf),
+}
+
+/// Atiende las peticiones de instantánea entre ticks de simulación.
+pub fn process_snapshot_requests(world: &mut World) {
+    let requests: Vec<SnapshotRequest> = world
+        .resource_mut::<Events<SnapshotRequest>>()
+        .drain()
+        .collect();
+
+    for request in requests {
+        match request {
+            SnapshotRequest::Save(path) => save_snapshot(world, &path),
+            SnapshotRequest::Load(path) => match WorldSnapshot::load(&path) {
+                Ok(snapshot) => {
+                    let (creatures, predators, plants) = (
+                        snapshot.creatures.len(),
+                        snapshot.predators.len(),
+                        snapshot.plants.len(),
+                    );
+                    let size = snapshot.bounds.size();
+                    snapshot.restore(world);
+                    // Con ventana, los límites siguen al tamaño de la ventana: se ajusta
+                    // para que no sustituya a los de la instantánea.
+                    if let Ok(mut window) = world.query::<&mut Window>().single_mut(world) {
+                        window.resolution.set(size.x, size.y);
+                    }
+                    info!(
+                        "📂 Instantánea cargada desde '{}' (🧬 {creatures} | 🦊 {predators} | 🌿 {plants})",
+                        path.display()
+                    );
+                }
+                Err(error) => error!("❌ No se pudo cargar la instantánea: {error}"),
+            },
+        }
+    }
+}
+
+/// Guarda una instantánea al cerrar la aplicación si así se configuró.
+pub fn save_snapshot_on_exit(world: &mut World) {
+    let exiting = world
+        .resource::<Events<AppExit>>()
+        .iter_current_update_events()
+        .next()
+        .is_some();
+    if !exiting {
+        return;
+    }
+
+    let settings = world.resource::<SnapshotSettings>();
+    if settings.save_on_exit {
+        let path = settings.path.clone();
+        save_snapshot(world, &path);
+    }
+}
+
+/// Teclas F5 (guardar) y F9 (cargar) sobre la ruta configurada.
+pub fn snapshot_keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<SnapshotSettings>,
+    mut requests: EventWriter<SnapshotRequest>,
+) {
+    if keys.just_pressed(KeyCode::F5) {
+        requests.write(SnapshotRequest::Save(settings.path.clone()));
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        requests.write(SnapshotRequest::Load(settings.path.clone()));
+    }
+}
+
+fn save_snapshot(world: &mut World, path: &PathBuf) {
+    match WorldSnapshot::capture(world).save(path) {
+        Ok(()) => info!("💾 Instantánea guardada en '{}'", path.display()),
+        Err(error) => error!("❌ No se pudo guardar la instantánea: {error}"),
+    }
+}
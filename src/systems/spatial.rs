@@ -0,0 +1,30 @@
+use crate::components::{Creature, Plant, Predator};
+use crate::config::SimConfig;
+use crate::resources::SpatialIndex;
+use crate::utils::spatial::SpatialGrid;
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Reconstruye el índice espacial con las posiciones actuales.
+pub fn rebuild_spatial_index(
+    config: Res<SimConfig>,
+    mut index: ResMut<SpatialIndex>,
+    plants: Query<(Entity, &Transform), With<Plant>>,
+    creatures: Query<(Entity, &Transform), With<Creature>>,
+    predators: Query<(Entity, &Transform), With<Predator>>,
+) {
+    let cell_size = config.spatial.cell_size;
+    fill_grid(&mut index.plants, cell_size, &plants);
+    fill_grid(&mut index.creatures, cell_size, &creatures);
+    fill_grid(&mut index.predators, cell_size, &predators);
+}
+
+fn fill_grid<F: QueryFilter>(
+    grid: &mut SpatialGrid,
+    cell_size: f32,
+    query: &Query<(Entity, &Transform), F>,
+) {
+    grid.reset(cell_size);
+    for (entity, transform) in query.iter() {
+        grid.insert(entity, transform.translation.truncate());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::resources::SimulationSpeed;
+use bevy::{app::FixedMain, prelude::*};
+use std::time::Duration;
+
+/// Máximo avance de tiempo virtual por frame a velocidad 1x.
+const BASE_MAX_DELTA: Duration = Duration::from_millis(250);
+
+/// Traslada la pausa y el multiplicador de `SimulationSpeed` al reloj virtual.
+pub fn apply_simulation_speed(speed: Res<SimulationSpeed>, mut time: ResMut<Time<Virtual>>) {
+    if !speed.is_changed() {
+        return;
+    }
+
+    if speed.is_paused() {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+
+    let multiplier = speed.multiplier();
+    time.set_relative_speed(multiplier);
+    // Sin ampliar el límite, a velocidades altas el reloj virtual se recortaría
+    // y la simulación no alcanzaría el multiplicador pedido.
+    time.set_max_delta(BASE_MAX_DELTA.mul_f32(multiplier.max(1.0)));
+}
+
+/// Ejecuta los ticks pedidos con `SimulationSpeed::step` mientras el reloj está en pausa.
+pub fn run_pending_steps(world: &mut World) {
+    let steps = world.resource_mut::<SimulationSpeed>().take_pending_steps();
+    if steps == 0 {
+        return;
+    }
+
+    for _ in 0..steps {
+        let timestep = world.resource::<Time<Fixed>>().timestep();
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        world.run_schedule(FixedMain);
+    }
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+/// Controles de teclado: Espacio pausa, `.` avanza un tick, `+`/`-` cambian la velocidad
+/// y `0` la devuelve a 1x.
+pub fn speed_keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut speed: ResMut<SimulationSpeed>,
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        speed.toggle_pause();
+    }
+    if keys.just_pressed(KeyCode::Period) {
+        speed.step();
+    }
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        speed.faster();
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        speed.slower();
+    }
+    if keys.any_just_pressed([KeyCode::Digit0, KeyCode::Numpad0]) {
+        speed.set_multiplier(1.0);
+    }
+}
@@ -1,81 +1,137 @@
-use crate::components::{Creature, Plant, Organism, Predator, State, Velocity};
+use crate::components::{Creature, Genes, Organism, Perception, State, Velocity};
+use crate::config::SimConfig;
+use crate::events::StateChangedEvent;
+use crate::resources::{PheromoneField, Species};
+use crate::systems::pheromones::{danger_scent, food_scent};
 use bevy::prelude::*;
 
 /// Sistema que decide en qué estado está cada criatura (comer, reproducirse o vagar)
 pub fn update_states(
-    mut query: Query<(&Transform, &mut State, &Creature, &Organism)>,
+    config: Res<SimConfig>,
+    mut changes: EventWriter<StateChangedEvent>,
+    mut query: Query<(
+        Entity,
+        &mut State,
+        &mut Velocity,
+        &Creature,
+        &Organism,
+        &Genes,
+    )>,
 ) {
-    for (transform, mut state, creature, organism) in query.iter_mut() {
-        let new_state = if organism.energy < 50.0 {
-            State::SeekingFood
-        } else if organism.energy > 120.0 && creature.time_since_reproduction > 5.0 {
-            State::Reproducing
-        } else {
-            State::Wandering
-        };
+    for (entity, mut state, mut velocity, creature, organism, genes) in query.iter_mut() {
+        // Los herbívoros con cerebro deciden en `brain_system`.
+        if genes.brain.is_some() {
+            continue;
+        }
+        let new_state = choose_state(&config, creature, organism, genes);
 
         if *state != new_state {
-            info!(
-                "🧠 Cambio de estado en criatura ({:?}): {:?} -> {:?}",
-                transform.translation, *state, new_state
-            );
+            changes.write(StateChangedEvent {
+                entity,
+                species: Species::Creature,
+                from: *state,
+                to: new_state,
+            });
             *state = new_state;
+
+            // Al dejar de buscar comida o de huir recuperan su velocidad de crucero.
+            if new_state == State::Wandering {
+                velocity.0 = velocity.0.normalize_or_zero() * genes.speed();
+            }
+        }
+    }
+}
+
+/// Estado que corresponde a un herbívoro sin cerebro según su energía y su espera
+/// desde la última reproducción.
+pub fn choose_state(
+    config: &SimConfig,
+    creature: &Creature,
+    organism: &Organism,
+    genes: &Genes,
+) -> State {
+    let creatures = &config.creatures;
+    let hunger_threshold = genes.get_or(Genes::HUNGER_THRESHOLD, creatures.hunger_threshold);
+    let reproduction_energy =
+        genes.get_or(Genes::REPRODUCTION_ENERGY, creatures.reproduction_energy);
+    if organism.energy < hunger_threshold {
+        State::SeekingFood
+    } else if organism.energy > reproduction_energy
+        && creature.time_since_reproduction > creatures.reproduction_cooldown
+    {
+        State::Reproducing
+    } else {
+        State::Wandering
+    }
+}
+
+/// Registra en el log los cambios de estado de las criaturas.
+pub fn log_state_changes(
+    mut changes: EventReader<StateChangedEvent>,
+    transforms: Query<&Transform>,
+) {
+    for change in changes.read() {
+        if change.species != Species::Creature {
+            continue;
         }
+        let Ok(transform) = transforms.get(change.entity) else {
+            continue;
+        };
+        let position = transform.translation;
+        info!(
+            "🧠 Cambio de estado en criatura ({:?}): {:?} -> {:?}",
+            position, change.from, change.to
+        );
     }
 }
 
-/// Sistema que mueve criaturas hambrientas hacia la comida más cercana
+/// Sistema que mueve criaturas hambrientas hacia la planta más cercana que ven o,
+/// si no ven ninguna, hacia donde más huele a comida
 pub fn seek_food_system(
-    mut creatures: Query<(&Transform, &mut Velocity, &State), With<Creature>>,
-    plants: Query<&Transform, With<Plant>>,
+    config: Res<SimConfig>,
+    field: Res<PheromoneField>,
+    mut creatures: Query<(&Transform, &mut Velocity, &State, &Genes, &Perception), With<Creature>>,
 ) {
-    for (creature_transform, mut velocity, state) in creatures.iter_mut() {
-        if *state != State::SeekingFood {
+    for (creature_transform, mut velocity, state, genes, perception) in creatures.iter_mut() {
+        if *state != State::SeekingFood || genes.brain.is_some() {
             continue;
         }
 
-        if let Some(closest_plant) = plants.iter().min_by(|a, b| {
-            let pos = creature_transform.translation.truncate();
-            let da = a.translation.truncate().distance_squared(pos);
-            let db = b.translation.truncate().distance_squared(pos);
-            da.total_cmp(&db)
-        }) {
-            let direction = (closest_plant.translation - creature_transform.translation)
-                .truncate()
-                .normalize_or_zero();
-            velocity.0 = direction * 50.0;
+        // Si no ven ni huelen ninguna planta siguen su rumbo hasta dar con alguna.
+        let pos = creature_transform.translation.truncate();
+        let direction = match &perception.plant {
+            Some(plant) => Some((plant.position - pos).normalize_or_zero()),
+            None => food_scent(&config, &field, pos),
+        };
+        if let Some(direction) = direction {
+            velocity.0 = direction * genes.speed() * config.creatures.seek_speed_factor;
         }
     }
 }
 
-/// Sistema que hace que las criaturas huyan de depredadores cercanos
+/// Sistema que hace que las criaturas huyan de los depredadores que ven cerca o,
+/// si no ven ninguno, del olor a peligro intenso
 pub fn avoid_predators_system(
-    predators: Query<&Transform, With<Predator>>,
-    mut creatures: Query<(&Transform, &mut Velocity, &State), With<Creature>>,
+    config: Res<SimConfig>,
+    field: Res<PheromoneField>,
+    mut creatures: Query<(&Transform, &mut Velocity, &State, &Genes, &Perception), With<Creature>>,
 ) {
-    for (creature_transform, mut velocity, state) in creatures.iter_mut() {
-        if *state != State::Wandering && *state != State::SeekingFood {
+    for (creature_transform, mut velocity, state, genes, perception) in creatures.iter_mut() {
+        if (*state != State::Wandering && *state != State::SeekingFood) || genes.brain.is_some() {
             continue;
         }
 
-        if let Some(closest_predator) = predators.iter().min_by(|a, b| {
-            let pos = creature_transform.translation.truncate();
-            let da = a.translation.truncate().distance_squared(pos);
-            let db = b.translation.truncate().distance_squared(pos);
-            da.total_cmp(&db)
-        }) {
-            let distance = creature_transform
-                .translation
-                .truncate()
-                .distance(closest_predator.translation.truncate());
-
-            if distance < 100.0 {
-                // Huir en dirección opuesta
-                let direction = (creature_transform.translation - closest_predator.translation)
-                    .truncate()
-                    .normalize_or_zero();
-                velocity.0 = direction * 80.0;
-            }
+        let pos = creature_transform.translation.truncate();
+        let direction = match perception
+            .predator
+            .filter(|p| p.distance < config.creatures.flee_radius)
+        {
+            // Huir en dirección opuesta
+            Some(predator) => Some((pos - predator.position).normalize_or_zero()),
+            None => danger_scent(&config, &field, pos),
+        };
+        if let Some(direction) = direction {
+            velocity.0 = direction * genes.speed() * config.creatures.flee_speed_factor;
         }
     }
 }
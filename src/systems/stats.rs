@@ -0,0 +1,23 @@
+use crate::events::{BirthEvent, DeathEvent};
+use crate::resources::{Species, Stats};
+use bevy::prelude::*;
+
+/// Acumula en `Stats` los nacimientos del tick y la generación máxima de los herbívoros.
+pub fn count_births(mut births: EventReader<BirthEvent>, mut stats: ResMut<Stats>) {
+    for birth in births.read() {
+        match birth.species {
+            Species::Creature => {
+                stats.total_reproductions += 1;
+                stats.max_generation = stats.max_generation.max(birth.generation);
+            }
+            Species::Predator => stats.predator_births += 1,
+        }
+    }
+}
+
+/// Acumula en `Stats` las muertes del tick por especie y causa.
+pub fn count_deaths(mut deaths: EventReader<DeathEvent>, mut stats: ResMut<Stats>) {
+    for death in deaths.read() {
+        stats.record_death(death.species, death.cause);
+    }
+}
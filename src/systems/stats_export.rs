@@ -0,0 +1,65 @@
+use crate::components::{Creature, Genes, Organism, Plant, Predator};
+use crate::neat::NeatState;
+use crate::resources::Stats;
+use crate::stats_export::{StatsRecorder, StatsSample, mean_and_variance};
+use bevy::prelude::*;
+
+/// Toma una muestra de la población cada intervalo y la añade al archivo de series temporales.
+pub fn record_stats_sample(
+    stats: Res<Stats>,
+    neat: Res<NeatState>,
+    mut recorder: ResMut<StatsRecorder>,
+    creatures: Query<(&Organism, &Genes), With<Creature>>,
+    predators: Query<(&Organism, &Genes), With<Predator>>,
+    plants: Query<&Plant>,
+) {
+    if !recorder.due(stats.simulation_time) {
+        return;
+    }
+
+    let (speed_mean, speed_variance) = mean_and_variance(creatures.iter().map(|(_, g)| g.speed()));
+    let (size_mean, size_variance) = mean_and_variance(creatures.iter().map(|(_, g)| g.size()));
+    let (predator_speed_mean, predator_speed_variance) =
+        mean_and_variance(predators.iter().map(|(_, g)| g.speed()));
+    let (predator_size_mean, predator_size_variance) =
+        mean_and_variance(predators.iter().map(|(_, g)| g.size()));
+    let (mean_generation, _) = mean_and_variance(
+        creatures
+            .iter()
+            .chain(predators.iter())
+            .map(|(organism, _)| organism.generation as f32),
+    );
+    let neat_genomes = || {
+        creatures
+            .iter()
+            .filter_map(|(_, genes)| genes.brain.as_ref()?.neat())
+    };
+    let (neat_hidden_mean, _) = mean_and_variance(neat_genomes().map(|g| g.hidden.len() as f32));
+    let (neat_connections_mean, _) =
+        mean_and_variance(neat_genomes().map(|g| g.enabled_connections() as f32));
+
+    let sample = StatsSample {
+        creatures: creatures.iter().len(),
+        predators: predators.iter().len(),
+        plants: plants.iter().len(),
+        speed_mean,
+        speed_variance,
+        size_mean,
+        size_variance,
+        predator_speed_mean,
+        predator_speed_variance,
+        predator_size_mean,
+        predator_size_variance,
+        mean_generation,
+        plant_biomass: plants.iter().map(|plant| plant.size).sum(),
+        neat_species: neat.species.len(),
+        neat_hidden_mean,
+        neat_connections_mean,
+        ..default()
+    }
+    .with_stats(&stats);
+
+    if let Err(error) = recorder.write(&sample) {
+        error!("❌ No se pudieron exportar las estadísticas; se desactiva la exportación: {error}");
+    }
+}
@@ -1,29 +1,48 @@
 pub mod factory;
+pub mod field;
+pub mod spatial;
 
+use crate::config::ColorGeneConfig;
 use bevy::prelude::*;
 use rand::prelude::*;
 
-/// Asigna un color HSL a una generación
-pub fn color_from_generation(r#gen: u32) -> Color {
-    let hue = (r#gen as f32 * 40.0) % 360.0;
-    Color::hsl(hue, 0.8, 0.6)
+/// Color de un fundador: tono al azar y saturación y luminosidad dentro de sus límites.
+pub fn random_color(config: &ColorGeneConfig, rng: &mut impl Rng) -> Color {
+    let hue = rng.random_range(0.0..360.0);
+    let sat = config.saturation_bounds.sample(rng);
+    let light = config.lightness_bounds.sample(rng);
+    Color::hsl(hue, sat, light)
 }
 
-/// Mutación simple de color (generación aleatoria cercana en HSL)
-pub fn mutate_color(_original: &Color) -> Color {
-    let mut rng = rand::rng();
-
-    // Generamos un nuevo color mutado
-    let hue = rng.random_range(0.0..=360.0);
-    let sat = rng.random_range(0.7..=0.9);
-    let light = rng.random_range(0.5..=0.7);
-    let mutated = Color::hsl(hue, sat, light);
+/// Mutación del color heredado: un pequeño paso en cada componente HSL, de modo
+/// que los descendientes conservan un tono parecido al de su linaje.
+pub fn mutate_color(original: &Color, config: &ColorGeneConfig, rng: &mut impl Rng) -> Color {
+    let parent = Hsla::from(*original);
+    let hue_step = config.hue_step;
+    let sat_step = config.saturation_step;
+    let light_step = config.lightness_step;
+    let hue = (parent.hue + rng.random_range(-hue_step..=hue_step)).rem_euclid(360.0);
+    let sat = config
+        .saturation_bounds
+        .clamp(parent.saturation + rng.random_range(-sat_step..=sat_step));
+    let light = config
+        .lightness_bounds
+        .clamp(parent.lightness + rng.random_range(-light_step..=light_step));
+    let mutated = Color::hsla(hue, sat, light, parent.alpha);
 
     // Log de la mutación
     info!(
-        "Mutación de color: H {:.1}, S {:.2}, L {:.2} → {:?}",
-        hue, sat, light, mutated
+        "🎨 Mutación de color: H {:.1} → {:.1}, S {:.2} → {:.2}, L {:.2} → {:.2}",
+        parent.hue, hue, parent.saturation, sat, parent.lightness, light
     );
 
     mutated
 }
+
+/// Si hay un marcador neutro configurado, el tono pasa a ser su valor módulo 360.
+pub fn apply_color_marker(color: Color, config: &ColorGeneConfig, marker: Option<f32>) -> Color {
+    match (&config.marker, marker) {
+        (Some(_), Some(value)) => Hsla::from(color).with_hue(value.rem_euclid(360.0)).into(),
+        _ => color,
+    }
+}
@@ -1,139 +1,246 @@
+use crate::brain::Brain;
+use crate::components::{Creature, Genes, Organism, Perception, Predator, State, Velocity};
+use crate::config::{GenomeConfig, SimConfig};
+use crate::lineage::OrganismId;
+use crate::neat::NeatState;
+use crate::utils::{apply_color_marker, mutate_color, random_color};
 use bevy::prelude::*;
 use rand::prelude::*;
-use crate::components::{Creature, Genes, Organism, Predator, State, Velocity};
-use crate::utils::{color_from_generation, mutate_color};
+use std::collections::BTreeMap;
 
-pub fn spawn_creature(commands: &mut Commands, generation: u32) {
-    let mut rng = rand::rng();
+/// Posición aleatoria dentro de la zona de aparición configurada.
+fn random_spawn_position(config: &SimConfig, rng: &mut impl Rng) -> Transform {
+    let population = &config.population;
+    Transform::from_xyz(
+        rng.random_range(-population.spawn_half_width..=population.spawn_half_width),
+        rng.random_range(-population.spawn_half_height..=population.spawn_half_height),
+        0.0,
+    )
+}
+
+/// Genes aleatorios dentro de los rangos iniciales de cada rasgo configurado.
+///
+/// Un cerebro NEAT parte de la topología mínima y recibe especie en `neat`.
+pub fn initial_genes(genome: &GenomeConfig, neat: &mut NeatState, rng: &mut impl Rng) -> Genes {
+    let traits: BTreeMap<String, f32> = genome
+        .traits
+        .iter()
+        .map(|t| (t.name.clone(), t.initial.sample(rng)))
+        .collect();
+    let color = random_color(&genome.color, rng);
+    let mut brain = genome
+        .brain
+        .as_ref()
+        .map(|brain| Brain::random(brain, &mut neat.innovations, rng));
+    neat.speciate(brain.as_mut(), genome.brain.as_ref());
+    Genes {
+        color: with_marker(genome, color, &traits),
+        traits,
+        brain,
+    }
+}
 
-    let speed = rng.random_range(20.0..60.0);
-    let size = rng.random_range(10.0..30.0);
-    let color = color_from_generation(generation);
-    let dir = Vec2::from_angle(rng.random_range(0.0..=std::f32::consts::TAU)) * speed;
+fn with_marker(genome: &GenomeConfig, color: Color, traits: &BTreeMap<String, f32>) -> Color {
+    let marker = genome
+        .color
+        .marker
+        .as_ref()
+        .and_then(|name| traits.get(name));
+    apply_color_marker(color, &genome.color, marker.copied())
+}
+
+pub fn spawn_creature(
+    commands: &mut Commands,
+    config: &SimConfig,
+    rng: &mut impl Rng,
+    genes: Genes,
+    id: OrganismId,
+    generation: u32,
+) {
+    let dir = Vec2::from_angle(rng.random_range(0.0..=std::f32::consts::TAU)) * genes.speed();
 
     commands.spawn((
-        Sprite {
-            color,
-            custom_size: Some(Vec2::splat(size)),
-            ..default()
-        },
-        Transform::from_xyz(
-            rng.random_range(-300.0..=300.0),
-            rng.random_range(-200.0..=200.0),
-            0.0,
-        ),
+        random_spawn_position(config, rng),
         GlobalTransform::default(),
         Visibility::Visible,
         Velocity(dir),
         Organism {
-            energy: 100.0,
+            id,
+            energy: config.creatures.initial_energy,
             age: 0.0,
             generation,
         },
         Creature {
             time_since_reproduction: 0.0,
         },
-        Genes { speed, size, color },
+        genes,
         State::Wandering,
+        Perception::default(),
     ));
 }
 
-pub fn spawn_child_creature(
-    commands: &mut Commands,
-    position: Vec3,
-    velocity: Vec2,
+/// Genes heredados de un progenitor: cada rasgo configurado muta con su
+/// probabilidad y distribución y se mantiene dentro de sus límites.
+///
+/// Un rasgo que el progenitor no tenía (añadido a la configuración a mitad de
+/// simulación) se sortea de su rango inicial; los que ya no están configurados se
+/// heredan sin cambios. El cerebro, si `genome.brain` está activo, muta sus pesos
+/// y, si es NEAT, quizá su topología; después se le asigna especie en `neat`.
+pub fn inherit_genes(
+    genome: &GenomeConfig,
     parent_genes: &Genes,
-    generation: u32
-) {
-    let mut rng = rand::rng();
+    neat: &mut NeatState,
+    rng: &mut impl Rng,
+) -> Genes {
+    let mut traits = parent_genes.traits.clone();
+    for t in &genome.traits {
+        let value = match parent_genes.get(&t.name) {
+            None => t.initial.sample(rng),
+            // Con probabilidad 1 no se consume número aleatorio para decidir.
+            Some(value) if t.mutation_rate >= 1.0 || rng.random::<f32>() < t.mutation_rate => {
+                value + t.mutation.sample(rng)
+            }
+            Some(value) => value,
+        };
+        traits.insert(t.name.clone(), t.bounds.clamp(value));
+    }
+    let color = mutate_color(&parent_genes.color, &genome.color, rng);
+    let mut brain = match (&genome.brain, &parent_genes.brain) {
+        (Some(config), Some(brain)) => Some(brain.mutate(config, &mut neat.innovations, rng)),
+        (Some(config), None) => Some(Brain::random(config, &mut neat.innovations, rng)),
+        (None, _) => None,
+    };
+    neat.speciate(brain.as_mut(), genome.brain.as_ref());
+    Genes {
+        color: with_marker(genome, color, &traits),
+        traits,
+        brain,
+    }
+}
 
-    let child_genes = Genes {
-        speed: (parent_genes.speed + rng.random_range(-5.0..=5.0)).clamp(10.0, 100.0),
-        size: (parent_genes.size + rng.random_range(-2.0..=2.0)).clamp(5.0, 50.0),
-        color: mutate_color(&parent_genes.color),
+/// Cruce uniforme: cada rasgo se toma al azar de uno de los dos progenitores, y el
+/// color de uno de ellos. En un cerebro NEAT, `a` es el progenitor más apto y aporta
+/// los genes que `b` no tiene. El resultado aún debe pasar por [`inherit_genes`] para mutar.
+pub fn crossover_genes(a: &Genes, b: &Genes, rng: &mut impl Rng) -> Genes {
+    let mut traits = a.traits.clone();
+    for (name, value) in &b.traits {
+        match traits.get_mut(name) {
+            Some(current) => {
+                if rng.random_bool(0.5) {
+                    *current = *value;
+                }
+            }
+            None => {
+                traits.insert(name.clone(), *value);
+            }
+        }
+    }
+    let color = if rng.random_bool(0.5) {
+        a.color
+    } else {
+        b.color
     };
+    let brain = match (&a.brain, &b.brain) {
+        (Some(a), Some(b)) => Some(Brain::crossover(a, b, rng)),
+        (brain, None) | (None, brain) => brain.clone(),
+    };
+    Genes {
+        traits,
+        color,
+        brain,
+    }
+}
 
-    commands.spawn((
-        Sprite {
-            color: child_genes.color,
-            custom_size: Some(Vec2::splat(child_genes.size)),
-            ..default()
-        },
-        Transform::from_translation(position),
-        GlobalTransform::default(),
-        Visibility::Visible,
-        Creature {
-            time_since_reproduction: 0.0,
-        },
-        Organism {
-            energy: 100.0,
-            age: 0.0,
-            generation,
-        },
-        Velocity(velocity),
-        child_genes,
-        State::Wandering,
-    ));
+pub fn spawn_child_creature(
+    commands: &mut Commands,
+    config: &SimConfig,
+    position: Vec3,
+    velocity: Vec2,
+    child_genes: Genes,
+    id: OrganismId,
+    generation: u32,
+) -> Entity {
+    commands
+        .spawn((
+            Transform::from_translation(position),
+            GlobalTransform::default(),
+            Visibility::Visible,
+            Creature {
+                time_since_reproduction: 0.0,
+            },
+            Organism {
+                id,
+                energy: config.creatures.initial_energy,
+                age: 0.0,
+                generation,
+            },
+            Velocity(velocity),
+            child_genes,
+            State::Wandering,
+            Perception::default(),
+        ))
+        .id()
 }
 
 pub fn spawn_child_predator(
     commands: &mut Commands,
+    config: &SimConfig,
     position: Vec3,
     velocity: Vec2,
+    child_genes: Genes,
+    id: OrganismId,
     generation: u32,
-) {
-    commands.spawn((
-        Sprite {
-            color: Color::srgb(1.0, 0.0, 0.0),
-            custom_size: Some(Vec2::splat(40.0)),
-            ..default()
-        },
-        Transform::from_translation(position),
-        GlobalTransform::default(),
-        Visibility::Visible,
-        Velocity(velocity),
-        Organism {
-            energy: 100.0,
-            age: 0.0,
-            generation,
-        },
-        Predator {
-            reproduction_cooldown: 10.0,
-        },
-        State::Wandering,
-    ));
+) -> Entity {
+    commands
+        .spawn((
+            Transform::from_translation(position),
+            GlobalTransform::default(),
+            Visibility::Visible,
+            Velocity(velocity),
+            Organism {
+                id,
+                energy: config.predators.initial_energy,
+                age: 0.0,
+                generation,
+            },
+            Predator {
+                reproduction_cooldown: config.predators.reproduction_cooldown,
+                grip: 0.0,
+            },
+            child_genes,
+            State::Wandering,
+            Perception::default(),
+        ))
+        .id()
 }
 
-pub fn spawn_predator(commands: &mut Commands, generation: u32) {
-    let mut rng = rand::rng();
-
-    let speed = 80.0;
-    let size = 40.0;
-    let color = Color::srgb(1.0, 0.0, 0.0);
-    let dir = Vec2::from_angle(rng.random_range(0.0..=std::f32::consts::TAU)) * speed;
+pub fn spawn_predator(
+    commands: &mut Commands,
+    config: &SimConfig,
+    rng: &mut impl Rng,
+    genes: Genes,
+    id: OrganismId,
+    generation: u32,
+) {
+    let dir = Vec2::from_angle(rng.random_range(0.0..=std::f32::consts::TAU)) * genes.speed();
 
     commands.spawn((
-        Sprite {
-            color,
-            custom_size: Some(Vec2::splat(size)),
-            ..default()
-        },
-        Transform::from_xyz(
-            rng.random_range(-300.0..=300.0),
-            rng.random_range(-200.0..=200.0),
-            0.0,
-        ),
+        random_spawn_position(config, rng),
         GlobalTransform::default(),
         Visibility::Visible,
         Velocity(dir),
         Organism {
-            energy: 100.0,
+            id,
+            energy: config.predators.initial_energy,
             age: 0.0,
             generation,
         },
         Predator {
             reproduction_cooldown: 0.0,
+            grip: 0.0,
         },
+        genes,
         State::ReproducingSeason,
+        Perception::default(),
     ));
 }
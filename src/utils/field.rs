@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Campo escalar 2D muestreado en una rejilla regular que cubre un rectángulo del mundo.
+///
+/// Cada celda guarda una intensidad no negativa. Las posiciones fuera del rectángulo
+/// se leen y escriben en la celda más próxima del borde.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScalarField {
+    cell_size: f32,
+    /// Esquina inferior izquierda, en coordenadas del mundo.
+    origin: [f32; 2],
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+    /// Copia de trabajo para la difusión; no forma parte del estado.
+    #[serde(skip)]
+    scratch: Vec<f32>,
+}
+
+impl ScalarField {
+    /// Campo vacío (todo a cero) que cubre el rectángulo `[min, max]`.
+    pub fn covering(min: Vec2, max: Vec2, cell_size: f32) -> Self {
+        let cell_size = cell_size.max(f32::EPSILON);
+        let (width, height) = Self::dimensions(min, max, cell_size);
+        Self {
+            cell_size,
+            origin: min.to_array(),
+            width,
+            height,
+            values: vec![0.0; width * height],
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Si la rejilla es exactamente la que crearía [`ScalarField::covering`].
+    pub fn covers(&self, min: Vec2, max: Vec2, cell_size: f32) -> bool {
+        let cell_size = cell_size.max(f32::EPSILON);
+        self.cell_size == cell_size
+            && self.origin == min.to_array()
+            && (self.width, self.height) == Self::dimensions(min, max, cell_size)
+    }
+
+    fn dimensions(min: Vec2, max: Vec2, cell_size: f32) -> (usize, usize) {
+        let cells = ((max - min) / cell_size).ceil().max(Vec2::ONE);
+        (cells.x as usize, cells.y as usize)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Intensidades por filas, empezando por la fila inferior.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Rectángulo `(min, max)` que cubren las celdas.
+    pub fn extent(&self) -> (Vec2, Vec2) {
+        let min = Vec2::from_array(self.origin);
+        let size = Vec2::new(self.width as f32, self.height as f32) * self.cell_size;
+        (min, min + size)
+    }
+
+    /// Mayor intensidad del campo (0 si está vacío).
+    pub fn max(&self) -> f32 {
+        self.values.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Añade `amount` a la celda que contiene `position`.
+    pub fn add(&mut self, position: Vec2, amount: f32) {
+        if let Some(index) = self.index_of(position) {
+            self.values[index] += amount;
+        }
+    }
+
+    /// Intensidad en la celda que contiene `position`.
+    pub fn value(&self, position: Vec2) -> f32 {
+        self.index_of(position)
+            .map_or(0.0, |index| self.values[index])
+    }
+
+    /// Gradiente por diferencias centradas en la celda de `position`, en intensidad
+    /// por unidad de distancia. En los bordes se usa la propia celda como vecina.
+    pub fn gradient(&self, position: Vec2) -> Vec2 {
+        let Some((x, y)) = self.cell_of(position) else {
+            return Vec2::ZERO;
+        };
+        let at = |x: usize, y: usize| self.values[y * self.width + x];
+        let dx = at((x + 1).min(self.width - 1), y) - at(x.saturating_sub(1), y);
+        let dy = at(x, (y + 1).min(self.height - 1)) - at(x, y.saturating_sub(1));
+        Vec2::new(dx, dy) / (2.0 * self.cell_size)
+    }
+
+    /// Dirección unitaria en la que más aumenta la intensidad, si en `position` llega
+    /// al menos a `threshold` y el campo no es plano.
+    pub fn uphill(&self, position: Vec2, threshold: f32) -> Option<Vec2> {
+        if self.is_empty() || self.value(position) < threshold {
+            return None;
+        }
+        self.gradient(position).try_normalize()
+    }
+
+    /// Un paso de difusión y desvanecimiento.
+    ///
+    /// Cada celda se acerca una fracción `diffusion` (0–1) a la media de sus cuatro
+    /// vecinas y después se multiplica por `retention`. Los bordes no dejan escapar
+    /// nada: una vecina fuera de la rejilla cuenta como la propia celda.
+    pub fn step(&mut self, diffusion: f32, retention: f32) {
+        let (width, height) = (self.width, self.height);
+        let diffusion = diffusion.clamp(0.0, 1.0);
+        self.scratch.clone_from(&self.values);
+        let old = &self.scratch;
+        for y in 0..height {
+            for x in 0..width {
+                let i = y * width + x;
+                let here = old[i];
+                let left = if x > 0 { old[i - 1] } else { here };
+                let right = if x + 1 < width { old[i + 1] } else { here };
+                let down = if y > 0 { old[i - width] } else { here };
+                let up = if y + 1 < height { old[i + width] } else { here };
+                let mean = (left + right + down + up) / 4.0;
+                let value = (here + diffusion * (mean - here)) * retention;
+                // Los restos ínfimos se descartan para no arrastrar números subnormales.
+                self.values[i] = if value < 1e-6 { 0.0 } else { value };
+            }
+        }
+    }
+
+    fn cell_of(&self, position: Vec2) -> Option<(usize, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+        let local = (position - Vec2::from_array(self.origin)) / self.cell_size;
+        let x = (local.x.floor().max(0.0) as usize).min(self.width - 1);
+        let y = (local.y.floor().max(0.0) as usize).min(self.height - 1);
+        Some((x, y))
+    }
+
+    fn index_of(&self, position: Vec2) -> Option<usize> {
+        self.cell_of(position).map(|(x, y)| y * self.width + x)
+    }
+}
@@ -0,0 +1,158 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+/// Rejilla espacial uniforme para consultas de vecinos en 2D.
+///
+/// Las entidades se agrupan por celda; una consulta solo recorre las celdas que
+/// toca su radio. El orden de los resultados depende únicamente del orden de
+/// inserción, así que las consultas son deterministas.
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+    /// Celdas extremas ocupadas, para acotar las búsquedas.
+    bounds: Option<(IVec2, IVec2)>,
+    len: usize,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(50.0)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::default(),
+            bounds: None,
+            len: 0,
+        }
+    }
+
+    /// Vacía la rejilla y ajusta el tamaño de celda.
+    pub fn reset(&mut self, cell_size: f32) {
+        self.cell_size = cell_size.max(f32::EPSILON);
+        self.cells.clear();
+        self.bounds = None;
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push((entity, position));
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+        self.len += 1;
+    }
+
+    /// Entidades a distancia estrictamente menor que `radius` de `center`.
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let (min, max) = match self.bounds {
+            Some((lo, hi)) => (
+                self.cell_of(center - Vec2::splat(radius)).max(lo),
+                self.cell_of(center + Vec2::splat(radius)).min(hi),
+            ),
+            // Rango vacío
+            None => (IVec2::ONE, IVec2::ZERO),
+        };
+        let radius_sq = radius * radius;
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, position)| position.distance_squared(center) < radius_sq)
+    }
+
+    /// Entidad más cercana a `center` dentro de `max_radius` que cumpla `accept`.
+    ///
+    /// Recorre anillos de celdas desde el centro y se detiene en cuanto ningún anillo
+    /// restante puede contener algo más cercano. Admite `max_radius` infinito.
+    pub fn nearest(
+        &self,
+        center: Vec2,
+        max_radius: f32,
+        mut accept: impl FnMut(Entity) -> bool,
+    ) -> Option<(Entity, Vec2)> {
+        self.nearest_where(center, max_radius, |entity, _| accept(entity))
+    }
+
+    /// Como [`nearest`](Self::nearest), pero `accept` recibe también la posición.
+    pub fn nearest_where(
+        &self,
+        center: Vec2,
+        max_radius: f32,
+        mut accept: impl FnMut(Entity, Vec2) -> bool,
+    ) -> Option<(Entity, Vec2)> {
+        let (lo, hi) = self.bounds?;
+        let origin = self.cell_of(center);
+        let mut best: Option<(Entity, Vec2, f32)> = None;
+
+        for ring in 0.. {
+            if ring > 0 {
+                // Distancia mínima posible a cualquier celda de este anillo.
+                let ring_distance = (ring - 1) as f32 * self.cell_size;
+                if ring_distance >= max_radius {
+                    break;
+                }
+                if let Some((_, _, best_sq)) = best
+                    && best_sq <= ring_distance * ring_distance
+                {
+                    break;
+                }
+            }
+
+            for cell in ring_cells(origin, ring) {
+                let Some(entries) = self.cells.get(&cell) else {
+                    continue;
+                };
+                for &(entity, position) in entries {
+                    let distance_sq = position.distance_squared(center);
+                    if best.is_none_or(|(_, _, best_sq)| distance_sq < best_sq)
+                        && accept(entity, position)
+                    {
+                        best = Some((entity, position, distance_sq));
+                    }
+                }
+            }
+
+            // El anillo ya cubre todas las celdas ocupadas.
+            let covered_min = origin - IVec2::splat(ring);
+            let covered_max = origin + IVec2::splat(ring);
+            if covered_min.cmple(lo).all() && covered_max.cmpge(hi).all() {
+                break;
+            }
+        }
+
+        best.filter(|(_, _, distance_sq)| *distance_sq < max_radius * max_radius)
+            .map(|(entity, position, _)| (entity, position))
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+}
+
+/// Celdas a distancia de Chebyshev exactamente `ring` de `origin`, en orden fijo.
+fn ring_cells(origin: IVec2, ring: i32) -> impl Iterator<Item = IVec2> {
+    (-ring..=ring).flat_map(move |dy| {
+        let edge = dy.abs() == ring;
+        let step = if edge { 1 } else { (2 * ring).max(1) as usize };
+        (-ring..=ring)
+            .step_by(step)
+            .map(move |dx| origin + IVec2::new(dx, dy))
+    })
+}